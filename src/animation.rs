@@ -0,0 +1,248 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::{input::BinArgs, render::Mode};
+
+/// Pause between terminal frames when `--delay` is not given.
+pub const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// Steps shown when `--steps` is not given; real inputs can take tens of thousands.
+pub const DEFAULT_STEPS: usize = 1000;
+
+/// A walk through a puzzle that can be drawn after every step.
+pub trait Animate {
+    /// The current state, as text ending in a newline.
+    fn frame(&self, mode: Mode) -> String;
+
+    /// Whether the walk has reached its end; [`Animate::advance`] is not called after that.
+    fn is_done(&self) -> bool;
+
+    fn advance(&mut self);
+}
+
+/// Where the frames go.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Target {
+    /// Redrawn in place on the terminal, pausing after each one.
+    Terminal { delay: Duration },
+    /// Written as numbered text files, `frame-000000.txt` onwards, replacing the frames of any
+    /// earlier run in the directory.
+    Directory(PathBuf),
+}
+
+/// Shows the frames of an [`Animate`] walk, up to a number of steps.
+#[derive(Debug)]
+pub struct Animator {
+    target: Target,
+    max_steps: usize,
+    frames: usize,
+}
+
+impl Animator {
+    pub fn new(target: Target, max_steps: usize) -> Self {
+        Self {
+            target,
+            max_steps,
+            frames: 0,
+        }
+    }
+
+    /// The animation asked for on the command line: `--animate`, redrawn every `--delay <ms>`
+    /// or written to `--frames <dir>`, for at most `--steps <n>` steps. `None` without
+    /// `--animate`.
+    pub fn from_args(args: &BinArgs) -> Result<Option<Self>> {
+        let delay = args.value("--delay");
+        let dir = args.value("--frames");
+        let steps = args.value("--steps");
+        if !args.has("--animate") {
+            if delay.or(dir).or(steps).is_some() {
+                bail!("--delay, --frames and --steps only apply with --animate");
+            }
+            return Ok(None);
+        }
+
+        let target = match (dir, delay) {
+            (Some(_), Some(_)) => bail!("Frames written with --frames have no --delay"),
+            (Some(dir), None) => Target::Directory(PathBuf::from(dir)),
+            (None, Some(ms)) => Target::Terminal {
+                delay: Duration::from_millis(
+                    ms.parse().map_err(|_| anyhow!("--delay must be milliseconds, got '{}'", ms))?,
+                ),
+            },
+            (None, None) => Target::Terminal { delay: DEFAULT_DELAY },
+        };
+        let max_steps = match steps {
+            Some(n) => n.parse().map_err(|_| anyhow!("--steps must be a number, got '{}'", n))?,
+            None => DEFAULT_STEPS,
+        };
+        Ok(Some(Self::new(target, max_steps)))
+    }
+
+    /// Frames are coloured on a terminal that takes colours; files always get plain text.
+    pub fn mode(&self) -> Mode {
+        match self.target {
+            Target::Terminal { .. } => Mode::for_stdout(),
+            Target::Directory(_) => Mode::Plain,
+        }
+    }
+
+    /// Frames shown so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    pub fn max_steps(&self) -> usize {
+        self.max_steps
+    }
+
+    /// Shows the starting state, then the state after each step until the walk is done or
+    /// the step limit is reached. Returns whether the limit cut the walk short.
+    pub fn play(&mut self, walk: &mut impl Animate) -> Result<bool> {
+        let mode = self.mode();
+        self.frame(&walk.frame(mode))?;
+        for _ in 0..self.max_steps {
+            if walk.is_done() {
+                return Ok(false);
+            }
+            walk.advance();
+            self.frame(&walk.frame(mode))?;
+        }
+        Ok(!walk.is_done())
+    }
+
+    pub fn frame(&mut self, text: &str) -> Result<()> {
+        match &self.target {
+            Target::Terminal { delay } => {
+                let mut stdout = io::stdout().lock();
+                if self.mode() == Mode::Coloured {
+                    // Back to the top left corner and clear the screen, to draw over the last frame.
+                    write!(stdout, "\x1b[H\x1b[2J{}", text)?;
+                } else {
+                    writeln!(stdout, "{}", text)?;
+                }
+                stdout.flush()?;
+                thread::sleep(*delay);
+            }
+            Target::Directory(dir) => {
+                if self.frames == 0 {
+                    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+                    remove_frames(dir)?;
+                }
+                let path = dir.join(frame_name(self.frames));
+                fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+fn frame_name(index: usize) -> String {
+    format!("frame-{:06}.txt", index)
+}
+
+/// Deletes the frame files in `dir`, so that a shorter run does not leave some of a longer
+/// one behind. Other files are kept.
+fn remove_frames(dir: &Path) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        let is_frame = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("frame-")?.strip_suffix(".txt"))
+            .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+        if is_frame {
+            fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to a limit.
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl Animate for Counter {
+        fn frame(&self, _mode: Mode) -> String {
+            format!("{}\n", self.value)
+        }
+
+        fn is_done(&self) -> bool {
+            self.value == self.limit
+        }
+
+        fn advance(&mut self) {
+            self.value += 1;
+        }
+    }
+
+    fn frames_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("advent2023-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn writes_numbered_frames() {
+        let dir = frames_dir("frames");
+        let mut animator = Animator::new(Target::Directory(dir.clone()), 10);
+        assert_eq!(animator.mode(), Mode::Plain);
+        assert!(!animator.play(&mut Counter { value: 0, limit: 3 }).unwrap());
+        assert_eq!(animator.frames(), 4);
+        assert_eq!(fs::read_to_string(dir.join("frame-000000.txt")).unwrap(), "0\n");
+        assert_eq!(fs::read_to_string(dir.join("frame-000003.txt")).unwrap(), "3\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn stops_at_the_step_limit() {
+        let dir = frames_dir("limit");
+        let mut animator = Animator::new(Target::Directory(dir.clone()), 2);
+        let mut counter = Counter { value: 0, limit: 5 };
+        assert!(animator.play(&mut counter).unwrap());
+        assert_eq!((animator.frames(), counter.value), (3, 2));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reaching_the_end_at_the_limit_is_not_cut_short() {
+        let dir = frames_dir("exact");
+        let mut animator = Animator::new(Target::Directory(dir.clone()), 2);
+        assert!(!animator.play(&mut Counter { value: 0, limit: 2 }).unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn frames_of_an_earlier_run_are_replaced() {
+        let dir = frames_dir("stale");
+        fs::create_dir_all(&dir).unwrap();
+        for file in ["frame-000000.txt", "frame-000009.txt", "notes.txt"] {
+            fs::write(dir.join(file), "old\n").unwrap();
+        }
+        let mut animator = Animator::new(Target::Directory(dir.clone()), 10);
+        animator.play(&mut Counter { value: 0, limit: 1 }).unwrap();
+
+        let mut files: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["frame-000000.txt", "frame-000001.txt", "notes.txt"]);
+        assert_eq!(fs::read_to_string(dir.join("frame-000000.txt")).unwrap(), "0\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
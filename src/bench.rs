@@ -0,0 +1,222 @@
+use std::{
+    fmt,
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Timed step of a day: reading the input, then solving each part on the parsed input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Parse => "parse",
+            Self::Part1 => "part1",
+            Self::Part2 => "part2",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Wall times of every run of one phase, sorted from fastest to slowest.
+#[derive(Clone, Debug)]
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self { samples }
+    }
+
+    pub fn runs(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.first().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let n = self.samples.len();
+        match n {
+            0 => Duration::ZERO,
+            _ if n % 2 == 1 => self.samples[n / 2],
+            _ => (self.samples[n / 2 - 1] + self.samples[n / 2]) / 2,
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::ZERO,
+            n => self.samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DayTimings {
+    pub parse: Timings,
+    pub part1: Timings,
+    pub part2: Timings,
+}
+
+impl DayTimings {
+    pub fn phase(&self, phase: Phase) -> &Timings {
+        match phase {
+            Phase::Parse => &self.parse,
+            Phase::Part1 => &self.part1,
+            Phase::Part2 => &self.part2,
+        }
+    }
+}
+
+/// Calls `f` `runs` times, timing each call on its own. The result is passed through
+/// `black_box` so the work cannot be optimised away.
+pub fn measure<T>(runs: usize, mut f: impl FnMut() -> T) -> Timings {
+    let samples = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Timings::new(samples)
+}
+
+/// Timings of every benchmarked day, in the order they ran.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub days: Vec<(u8, DayTimings)>,
+}
+
+impl Report {
+    fn rows(&self) -> impl Iterator<Item = (u8, Phase, &Timings)> {
+        self.days
+            .iter()
+            .flat_map(|(day, timings)| Phase::ALL.map(|phase| (*day, phase, timings.phase(phase))))
+    }
+
+    pub fn table(&self) -> String {
+        let mut out = format!("{:<4} {:<6} {:>6} {:>12} {:>12} {:>12}\n", "Day", "Phase", "Runs", "Min", "Median", "Mean");
+        for (day, phase, timings) in self.rows() {
+            let _ = writeln!(
+                out,
+                "{:<4} {:<6} {:>6} {:>12} {:>12} {:>12}",
+                format!("{:02}", day),
+                phase,
+                timings.runs(),
+                format!("{:.2?}", timings.min()),
+                format!("{:.2?}", timings.median()),
+                format!("{:.2?}", timings.mean()),
+            );
+        }
+        out
+    }
+
+    /// One object per day and phase, times in nanoseconds.
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = self
+            .rows()
+            .map(|(day, phase, timings)| {
+                format!(
+                    "    {{\"day\": {}, \"phase\": \"{}\", \"runs\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}}}",
+                    day,
+                    phase,
+                    timings.runs(),
+                    timings.min().as_nanos(),
+                    timings.median().as_nanos(),
+                    timings.mean().as_nanos()
+                )
+            })
+            .collect();
+        format!("{{\n  \"results\": [\n{}\n  ]\n}}\n", rows.join(",\n"))
+    }
+
+    /// Same columns as the JSON report, with a header line.
+    pub fn to_csv(&self) -> String {
+        let mut out = "day,phase,runs,min_ns,median_ns,mean_ns\n".to_string();
+        for (day, phase, timings) in self.rows() {
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                day,
+                phase,
+                timings.runs(),
+                timings.min().as_nanos(),
+                timings.median().as_nanos(),
+                timings.mean().as_nanos()
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(micros: &[u64]) -> Timings {
+        Timings::new(micros.iter().map(|us| Duration::from_micros(*us)).collect())
+    }
+
+    #[test]
+    fn statistics_of_odd_sample_count() {
+        let t = timings(&[30, 10, 20]);
+        assert_eq!(t.min(), Duration::from_micros(10));
+        assert_eq!(t.median(), Duration::from_micros(20));
+        assert_eq!(t.mean(), Duration::from_micros(20));
+    }
+
+    #[test]
+    fn median_of_even_sample_count_is_the_middle_average() {
+        let t = timings(&[40, 10, 20, 90]);
+        assert_eq!(t.median(), Duration::from_micros(30));
+        assert_eq!(t.mean(), Duration::from_micros(40));
+    }
+
+    /// Day 7 with a single sample per part and an even split for parsing.
+    fn report() -> Report {
+        let day = DayTimings {
+            parse: timings(&[3, 1]),
+            part1: timings(&[5]),
+            part2: timings(&[7]),
+        };
+        Report { days: vec![(7, day)] }
+    }
+
+    #[test]
+    fn json_report() {
+        let expected = r#"{
+  "results": [
+    {"day": 7, "phase": "parse", "runs": 2, "min_ns": 1000, "median_ns": 2000, "mean_ns": 2000},
+    {"day": 7, "phase": "part1", "runs": 1, "min_ns": 5000, "median_ns": 5000, "mean_ns": 5000},
+    {"day": 7, "phase": "part2", "runs": 1, "min_ns": 7000, "median_ns": 7000, "mean_ns": 7000}
+  ]
+}
+"#;
+        assert_eq!(report().to_json(), expected);
+    }
+
+    #[test]
+    fn csv_report() {
+        let expected = "\
+day,phase,runs,min_ns,median_ns,mean_ns
+7,parse,2,1000,2000,2000
+7,part1,1,5000,5000,5000
+7,part2,1,7000,7000,7000
+";
+        assert_eq!(report().to_csv(), expected);
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul, Sub},
+};
+
+/// Unsigned integer of any size, for answers that overflow the primitive types.
+///
+/// Only the operations the puzzles need are provided. Subtraction panics if the result would
+/// be negative, like it does for the primitive unsigned types in debug builds.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BigUint {
+    /// Base 2^32 digits, least significant first, with no trailing zeros.
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Reads a string of decimal digits. Signs, separators and empty strings are rejected.
+    pub fn parse_decimal(digits: &str) -> Option<Self> {
+        if digits.is_empty() {
+            return None;
+        }
+        let mut value = Self::zero();
+        for c in digits.chars() {
+            let digit = c.to_digit(10)?;
+            value.mul_add_small(10, digit);
+        }
+        Some(value)
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [lo] => Some(lo as u64),
+            [lo, hi] => Some((hi as u64) << 32 | lo as u64),
+            _ => None,
+        }
+    }
+
+    /// Number of bits needed to write the value; zero needs none.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            None => 0,
+            Some(top) => self.limbs.len() as u64 * 32 - top.leading_zeros() as u64,
+        }
+    }
+
+    /// Largest integer whose square is at most `self`, found one bit at a time.
+    pub fn isqrt(&self) -> Self {
+        let mut rem = self.clone();
+        let mut root = Self::zero();
+        // Highest power of four not above the value.
+        let mut bit = Self::power_of_two(self.bits().saturating_sub(1) & !1);
+
+        while !bit.is_zero() && !self.is_zero() {
+            let candidate = &root + &bit;
+            root = root.shr(1);
+            if rem >= candidate {
+                rem = &rem - &candidate;
+                root = &root + &bit;
+            }
+            bit = bit.shr(2);
+        }
+        root
+    }
+
+    /// Quotient and remainder of a division by a small divisor.
+    pub fn div_rem(&self, divisor: u32) -> (Self, u32) {
+        let mut quotient = self.clone();
+        let rem = quotient.div_rem_small(divisor);
+        (quotient, rem)
+    }
+
+    fn power_of_two(exp: u64) -> Self {
+        let mut limbs = vec![0; exp as usize / 32];
+        limbs.push(1 << (exp % 32));
+        Self { limbs }
+    }
+
+    fn shr(&self, bits: u32) -> Self {
+        debug_assert!(bits < 32);
+        if bits == 0 {
+            return self.clone();
+        }
+        let limbs = (0..self.limbs.len())
+            .map(|i| {
+                let hi = self.limbs.get(i + 1).copied().unwrap_or(0);
+                self.limbs[i] >> bits | hi << (32 - bits)
+            })
+            .collect();
+        Self::from_limbs(limbs)
+    }
+
+    /// `self = self * factor + addend`.
+    fn mul_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in &mut self.limbs {
+            let value = *limb as u64 * factor as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry != 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    /// Divides in place and returns the remainder.
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut rem = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = rem << 32 | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            rem = value % divisor as u64;
+        }
+        self.trim();
+        rem as u32
+    }
+
+    fn from_limbs(limbs: Vec<u32>) -> Self {
+        let mut value = Self { limbs };
+        value.trim();
+        value
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_limbs(vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(rhs.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let a = self.limbs.get(i).copied().unwrap_or(0) as u64;
+            let b = rhs.limbs.get(i).copied().unwrap_or(0) as u64;
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, rhs: &BigUint) -> BigUint {
+        assert!(*self >= *rhs, "attempt to subtract {} from {}", rhs, self);
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, a) in self.limbs.iter().enumerate() {
+            let b = rhs.limbs.get(i).copied().unwrap_or(0) as i64;
+            let mut diff = *a as i64 - b - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += 1 << 32;
+                borrow = 1;
+            }
+            limbs.push(diff as u32);
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + rhs.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in rhs.limbs.iter().enumerate() {
+                let value = limbs[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                limbs[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            limbs[i + rhs.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Nine decimal digits at a time, least significant chunk first.
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(1_000_000_000));
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(digits: &str) -> BigUint {
+        BigUint::parse_decimal(digits).unwrap()
+    }
+
+    #[test]
+    fn decimal_round_trip() {
+        for digits in ["0", "7", "4294967296", "18446744073709551616", "123456789012345678901234567890"] {
+            assert_eq!(big(digits).to_string(), digits);
+        }
+        assert_eq!(big("000120").to_string(), "120");
+        assert_eq!(BigUint::parse_decimal(""), None);
+        assert_eq!(BigUint::parse_decimal("-1"), None);
+    }
+
+    #[test]
+    fn arithmetic_matches_u128() {
+        let values = [0u64, 1, 2, 0xffff_ffff, 1 << 32, 123_456_789_012, u64::MAX];
+        for a in values {
+            for b in values {
+                let (x, y) = (BigUint::from(a), BigUint::from(b));
+                assert_eq!((&x + &y).to_string(), (a as u128 + b as u128).to_string());
+                assert_eq!((&x * &y).to_string(), (a as u128 * b as u128).to_string());
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                if a >= b {
+                    assert_eq!((&x - &y).to_u64(), Some(a - b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn to_u64_only_when_it_fits() {
+        assert_eq!(big("18446744073709551615").to_u64(), Some(u64::MAX));
+        assert_eq!(big("18446744073709551616").to_u64(), None);
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        for n in 0..300u64 {
+            let root = BigUint::from(n).isqrt().to_u64().unwrap();
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "isqrt({}) = {}", n, root);
+        }
+        let square = big("1000000000000000000000000000000000000000");
+        assert_eq!(square.isqrt().to_string(), "31622776601683793319");
+        let root = big("123456789012345678901234567890");
+        let square = &root * &root;
+        assert_eq!(square.isqrt(), root);
+        assert_eq!((&square - &BigUint::from(1)).isqrt(), &root - &BigUint::from(1));
+    }
+}
@@ -0,0 +1,256 @@
+use std::{env, fs};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use advent2023::{
+    bench::Report,
+    d08,
+    input::InputSource,
+    ledger::{Entry, Ledger, Verdict},
+    runner::run_day,
+    site::{Config, FetchOutcome, Site, SubmitOutcome},
+    solution::{find_solution, Part, Solution, SOLUTIONS},
+};
+
+/// Keep in sync with USAGE.
+const DEFAULT_BENCH_RUNS: usize = 10;
+
+const USAGE: &str = "\
+Usage:
+    aoc run <day> [<part>] [--input <file>|-]
+    aoc run --all
+    aoc fetch <day>
+    aoc submit <day> <part>
+    aoc bench [<day>] [--runs <n>] [--input <file>|-] [--json <file>] [--csv <file>]
+    aoc viz 8 --dot [--input <file>|-]
+
+Inputs are read from $AOC_INPUTS/dNN-input.txt (default ./src/bin/inputs) unless --input is given.
+`fetch` downloads into the same directory using AOC_SESSION (or the config file).
+`submit` records every verdict in the ledger ($AOC_LEDGER, default $AOC_INPUTS/ledger.tsv);
+`run` flags answers that differ from a recorded correct one.
+`bench` times parsing and each part separately (10 runs by default); build with --release.
+`viz 8 --dot` prints the day 8 network as Graphviz, with each ghost's loop coloured.
+
+The dNNpM binaries take `--input <file>|-` too, plus options of their own:
+    d03, d10, d11  --visualize          draw the grid in colour on stdout
+                   --image <file>       save the same picture as .ppm or .png
+    d08, d10       --animate            replay the walk step by step
+                   --delay <ms>         pause between frames (100 by default)
+                   --frames <dir>       write numbered frame files instead
+                   --steps <n>          stop after n steps (1000 by default)
+    d10p2          --method <name>      pick or scanline
+    d07            --explain            print the ranked hands";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (positional, input) = split_input_flag(&args)?;
+
+    match positional.as_slice() {
+        ["run", "--all"] if input.is_none() => {
+            let mut regressions = 0;
+            let mut failures = 0;
+            for solution in SOLUTIONS {
+                match run_day(solution, &Part::ALL, &InputSource::Day(solution.day())) {
+                    Ok(count) => regressions += count,
+                    Err(err) => {
+                        eprintln!("Day {:02}: {:#}", solution.day(), err);
+                        failures += 1;
+                    }
+                }
+            }
+            if failures > 0 {
+                bail!("{} of {} days failed", failures, SOLUTIONS.len());
+            }
+            check_regressions(regressions)
+        }
+        ["run", day] => {
+            let solution = parse_day(day)?;
+            let input = input.unwrap_or(InputSource::Day(solution.day()));
+            check_regressions(run_day(solution, &Part::ALL, &input)?)
+        }
+        ["run", day, part] => {
+            let solution = parse_day(day)?;
+            let input = input.unwrap_or(InputSource::Day(solution.day()));
+            check_regressions(run_day(solution, &[parse_part(part)?], &input)?)
+        }
+        ["fetch", day] if input.is_none() => fetch(parse_puzzle_day(day)?),
+        ["submit", day, part] if input.is_none() => submit(parse_day(day)?, parse_part(part)?),
+        ["bench", options @ ..] => bench(options, input.as_ref()),
+        ["viz", day, options @ ..] => viz(parse_day(day)?, options, input),
+        _ => bail!(USAGE),
+    }
+}
+
+fn split_input_flag(args: &[String]) -> Result<(Vec<&str>, Option<InputSource>)> {
+    let mut positional = vec![];
+    let mut input = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--input" {
+            let value = iter.next().ok_or_else(|| anyhow!("--input needs a file or '-'"))?;
+            input = Some(InputSource::from_arg(value));
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+
+    Ok((positional, input))
+}
+
+/// Removes `flag` and the value following it from `args`.
+fn take_option<'a>(args: &mut Vec<&'a str>, flag: &str) -> Result<Option<&'a str>> {
+    let Some(idx) = args.iter().position(|arg| *arg == flag) else {
+        return Ok(None);
+    };
+    if idx + 1 == args.len() {
+        bail!("{} needs a value", flag);
+    }
+    let value = args.remove(idx + 1);
+    args.remove(idx);
+    Ok(Some(value))
+}
+
+fn check_regressions(regressions: usize) -> Result<()> {
+    if regressions > 0 {
+        bail!("{} answer(s) differ from the ledger", regressions);
+    }
+    Ok(())
+}
+
+fn bench(options: &[&str], input: Option<&InputSource>) -> Result<()> {
+    let mut args = options.to_vec();
+    let runs = match take_option(&mut args, "--runs")? {
+        Some(arg) => arg
+            .parse::<usize>()
+            .ok()
+            .filter(|runs| *runs > 0)
+            .ok_or_else(|| anyhow!("--runs needs a positive number, got '{}'", arg))?,
+        None => DEFAULT_BENCH_RUNS,
+    };
+    let json = take_option(&mut args, "--json")?;
+    let csv = take_option(&mut args, "--csv")?;
+
+    let solutions = match args.as_slice() {
+        [] if input.is_none() => SOLUTIONS.to_vec(),
+        [day] => vec![parse_day(day)?],
+        _ => bail!(USAGE),
+    };
+
+    let mut report = Report::default();
+    for solution in solutions {
+        let day = solution.day();
+        let timings = match input {
+            Some(source) => source.read(),
+            None => InputSource::Day(day).read(),
+        }
+        .and_then(|text| solution.bench(&text, runs));
+
+        match timings {
+            Ok(timings) => report.days.push((day, timings)),
+            Err(err) => eprintln!("Day {:02}: {:#}", day, err),
+        }
+    }
+    if report.days.is_empty() {
+        bail!("Nothing to benchmark");
+    }
+
+    print!("{}", report.table());
+    if let Some(path) = json {
+        fs::write(path, report.to_json()).with_context(|| format!("Could not write {}", path))?;
+    }
+    if let Some(path) = csv {
+        fs::write(path, report.to_csv()).with_context(|| format!("Could not write {}", path))?;
+    }
+    Ok(())
+}
+
+fn viz(solution: &dyn Solution, options: &[&str], input: Option<InputSource>) -> Result<()> {
+    let day = solution.day();
+    let render: fn(&str) -> Result<String> = match (day, options) {
+        (8, ["--dot"]) => |text| Ok(d08::ghost_dot(&d08::parse_input(text)?)),
+        _ => bail!("Day {} has no such visualization\n\n{}", day, USAGE),
+    };
+    let text = input.unwrap_or(InputSource::Day(day)).read()?;
+    print!("{}", render(&text)?);
+    Ok(())
+}
+
+fn fetch(day: u8) -> Result<()> {
+    let site = Site::new(Config::load()?);
+    match site.fetch_input(day)? {
+        FetchOutcome::Cached(path) => println!("Day {:02} input already cached at {}", day, path.display()),
+        FetchOutcome::Downloaded(path) => println!("Day {:02} input saved to {}", day, path.display()),
+    }
+    Ok(())
+}
+
+fn submit(solution: &dyn Solution, part: Part) -> Result<()> {
+    let day = solution.day();
+    let input = InputSource::Day(day).read()?;
+    let answer = solution.solve(part, &input)?;
+
+    let mut ledger = Ledger::load()?;
+    if let Some(correct) = ledger.correct_answer(day, part) {
+        if correct == answer {
+            println!("Day {:02} part {}: {} is already recorded as correct", day, part, answer);
+            return Ok(());
+        }
+        bail!(
+            "Day {:02} part {} was already solved with {}, refusing to submit {}",
+            day,
+            part,
+            correct,
+            answer
+        );
+    }
+    if let Some(verdict) = ledger
+        .previous_verdict(day, part, &answer)
+        .or_else(|| ledger.out_of_known_bounds(day, part, &answer))
+    {
+        bail!("Day {:02} part {}: {} is known to be {}, not submitting", day, part, answer, verdict);
+    }
+
+    println!("Submitting {} for day {:02} part {}", answer, day, part);
+    let site = Site::new(Config::load()?);
+    match site.submit_answer(day, part, &answer)? {
+        SubmitOutcome::Verdict(verdict) => {
+            ledger.record(Entry {
+                day,
+                part,
+                answer,
+                verdict,
+            })?;
+            println!("Verdict: {}", verdict);
+            if verdict != Verdict::Correct {
+                bail!("Answer rejected");
+            }
+            Ok(())
+        }
+        SubmitOutcome::Wait(Some(remaining)) => bail!("Submitted too recently, wait {:?}", remaining),
+        SubmitOutcome::Wait(None) => bail!("Submitted too recently, try again later"),
+        SubmitOutcome::WrongLevel => bail!("Day {:02} part {} is already solved or still locked", day, part),
+        SubmitOutcome::Unrecognized(text) => bail!("Unrecognized response: {}", text),
+    }
+}
+
+fn parse_puzzle_day(arg: &str) -> Result<u8> {
+    arg.parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| anyhow!("Day must be between 1 and 25, got '{}'", arg))
+}
+
+fn parse_day(arg: &str) -> Result<&'static dyn Solution> {
+    arg.parse::<u8>()
+        .ok()
+        .and_then(find_solution)
+        .ok_or_else(|| anyhow!("No solution for day '{}'", arg))
+}
+
+fn parse_part(arg: &str) -> Result<Part> {
+    arg.parse::<u8>()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| anyhow!("Part must be 1 or 2, got '{}'", arg))
+}
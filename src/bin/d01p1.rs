@@ -1,20 +1,7 @@
-fn main() {
-    let input = include_str!("./inputs/d01-input.txt");
+use advent2023::{d01::solve_part1, input::source_from_args, runner::report_answer, solution::Part};
 
-    let mut result = 0;
-
-    for line in input.lines() {
-        let first_digit = line
-            .chars()
-            .find_map(|c| c.to_digit(10))
-            .expect("Digit not found in line");
-        let last_digit = line
-            .chars()
-            .rev()
-            .find_map(|c| c.to_digit(10))
-            .expect("Digit not found in line");
-        result = result + (first_digit * 10 + last_digit);
-    }
-
-    println!("{}", result);
+fn main() -> anyhow::Result<()> {
+    let source = source_from_args(1)?;
+    let answer = solve_part1(&source.read()?)?;
+    report_answer(&source, 1, Part::One, answer)
 }
@@ -1,33 +1,7 @@
-use std::collections::HashSet;
+use advent2023::{d04::solve_part1, input::source_from_args, runner::report_answer, solution::Part};
 
-
-fn main() {
-    let input = include_str!("./inputs/d04-input.txt");
-    let result: usize = input.lines().map(game_score).sum();
-
-    println!("Answer: {}", result);
-}
-
-fn game_score(input: &str) -> usize {
-    let (_, input) = input.split_once(':').expect("Could not split by ':'");
-    let (left, right) = input.split_once('|').expect("Could not split by '|'");
-
-    let winners = parse_numbers_list(left);
-    let numbers = parse_numbers_list(right);
-
-    let count = winners.intersection(&numbers).count();
-
-    real_score(count)
-}
-
-fn parse_numbers_list(input: &str) -> HashSet<u32> {
-    input.trim().split_whitespace().map(|s| s.parse::<u32>().unwrap()).collect()
-}
-
-fn real_score(wins: usize) -> usize {
-    if wins > 0 {
-        1 << (wins - 1)
-    } else {
-        0
-    }
+fn main() -> anyhow::Result<()> {
+    let source = source_from_args(4)?;
+    let answer = solve_part1(&source.read()?)?;
+    report_answer(&source, 4, Part::One, answer)
 }
@@ -0,0 +1,284 @@
+use std::{fs, iter, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    geometry::Point,
+    render::{Colour, Render},
+};
+
+pub type Rgb = [u8; 3];
+
+/// Side of the square of pixels drawn for each tile by the `--image` option of the binaries.
+pub const BLOCK: usize = 4;
+
+/// Colour of tiles a view leaves uncoloured.
+pub const BACKGROUND: Rgb = [24, 24, 24];
+
+impl Colour {
+    /// The colour as drawn in images, close to common terminal palettes.
+    pub fn rgb(&self) -> Rgb {
+        match self {
+            Self::Red => [205, 49, 49],
+            Self::Green => [13, 188, 121],
+            Self::Yellow => [229, 229, 16],
+            Self::Blue => [36, 114, 200],
+            Self::Magenta => [188, 63, 188],
+            Self::Cyan => [17, 168, 205],
+            Self::Grey => [102, 102, 102],
+        }
+    }
+}
+
+/// An RGB image, stored row by row.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Bitmap {
+    /// Draws every tile of `view` as a `scale` by `scale` block in the tile's colour.
+    pub fn from_render<R: Render>(view: &R, scale: usize) -> Self {
+        let grid = view.grid();
+        let (width, height) = (grid.cols() * scale, grid.rows() * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in 0..grid.rows() {
+            let line: Vec<Rgb> = (0..grid.cols())
+                .flat_map(|col| {
+                    let pos = Point::new(row as i64, col as i64);
+                    let rgb = view.colour(pos, &grid[pos]).map_or(BACKGROUND, |colour| colour.rgb());
+                    iter::repeat_n(rgb, scale)
+                })
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Self { width, height, pixels }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Binary PPM (P6): a short text header followed by the raw pixels.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().flatten());
+        out
+    }
+
+    /// PNG with 8-bit RGB pixels. The image data is stored without compression, which keeps the
+    /// encoder short at the price of files about as large as the PPM.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for line in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            // Filter type 0: the row as is.
+            raw.push(0);
+            raw.extend(line.iter().flatten());
+        }
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // Bit depth 8, colour type 2 (RGB), default compression and filtering, no interlacing.
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut out = PNG_SIGNATURE.to_vec();
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    /// Writes the image in the format named by the file's extension, `.ppm` or `.png`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => self.to_ppm(),
+            Some("png") => self.to_png(),
+            _ => bail!("Cannot tell the image format of {}, use a .ppm or .png file", path.display()),
+        };
+        fs::write(path, bytes).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Largest payload of a stored deflate block.
+const STORED_BLOCK: usize = 65535;
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// A zlib stream holding `data` in uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, no preset dictionary; the check bits make 0x7801 a multiple of 31.
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+/// CRC-32 as used by PNG (and zip, gzip): reflected, polynomial 0xEDB88320.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Adler-32, the checksum ending a zlib stream.
+pub fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 bytes is the most that can be summed before `b` could overflow.
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    /// '#' in red, anything else uncoloured.
+    struct Walls(Grid<char>);
+
+    impl Render for Walls {
+        type Cell = char;
+
+        fn grid(&self) -> &Grid<char> {
+            &self.0
+        }
+
+        fn glyph(&self, _pos: Point, cell: &char) -> char {
+            *cell
+        }
+
+        fn colour(&self, _pos: Point, cell: &char) -> Option<Colour> {
+            (*cell == '#').then_some(Colour::Red)
+        }
+    }
+
+    /// Payload of a zlib stream made only of stored blocks, checking lengths and the checksum.
+    fn inflate_stored(stream: &[u8]) -> Vec<u8> {
+        assert_eq!((stream[0] as u16 * 256 + stream[1] as u16) % 31, 0);
+        let mut data = vec![];
+        let mut at = 2;
+        loop {
+            let header = stream[at];
+            let len = u16::from_le_bytes([stream[at + 1], stream[at + 2]]);
+            let nlen = u16::from_le_bytes([stream[at + 3], stream[at + 4]]);
+            assert_eq!((header & 0b110, len), (0, !nlen));
+            data.extend(&stream[at + 5..at + 5 + len as usize]);
+            at += 5 + len as usize;
+            if header & 1 == 1 {
+                break;
+            }
+        }
+        assert_eq!(stream[at..], adler32(&data).to_be_bytes());
+        data
+    }
+
+    /// Chunks of a PNG file as (type, data), checking every CRC.
+    fn png_chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+        assert_eq!(png[..8], PNG_SIGNATURE);
+        let mut chunks = vec![];
+        let mut at = 8;
+        while at < png.len() {
+            let len = u32::from_be_bytes(png[at..at + 4].try_into().unwrap()) as usize;
+            let body = &png[at + 4..at + 8 + len];
+            let crc = u32::from_be_bytes(png[at + 8 + len..at + 12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(body));
+            chunks.push((String::from_utf8(body[..4].to_vec()).unwrap(), body[4..].to_vec()));
+            at += 12 + len;
+        }
+        chunks
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(&[0xff; 100_000]), 0x149a_302c);
+    }
+
+    #[test]
+    fn tiles_become_pixel_blocks() {
+        let bitmap = Bitmap::from_render(&Walls(Grid::parse("#.\n.#", Some).unwrap()), 2);
+        assert_eq!((bitmap.width(), bitmap.height()), (4, 4));
+        assert_eq!(bitmap.pixel(1, 1), Colour::Red.rgb());
+        assert_eq!(bitmap.pixel(2, 1), BACKGROUND);
+        assert_eq!(bitmap.pixel(3, 2), Colour::Red.rgb());
+
+        let ppm = bitmap.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+    }
+
+    #[test]
+    fn png_round_trip() {
+        // Large enough to need several stored blocks.
+        let text = "#.#.\n".repeat(150) + "....";
+        let bitmap = Bitmap::from_render(&Walls(Grid::parse(&text, Some).unwrap()), 30);
+        let chunks = png_chunks(&bitmap.to_png());
+        let kinds: Vec<&str> = chunks.iter().map(|(kind, _)| kind.as_str()).collect();
+        assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 120, 0, 0, 17, 178, 8, 2, 0, 0, 0]);
+
+        let raw = inflate_stored(&chunks[1].1);
+        let row_len = 1 + 3 * bitmap.width();
+        assert_eq!(raw.len(), row_len * bitmap.height());
+        for (y, row) in raw.chunks(row_len).enumerate() {
+            assert_eq!(row[0], 0);
+            for (x, rgb) in row[1..].chunks(3).enumerate() {
+                assert_eq!(rgb, bitmap.pixel(x, y), "pixel {}, {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_data_still_ends_the_stream() {
+        assert_eq!(inflate_stored(&zlib_stored(&[])), []);
+    }
+}
@@ -0,0 +1,301 @@
+use std::{cmp::Ordering, collections::HashMap, error, fmt};
+
+pub type Card = char;
+
+/// Kind of a hand, weakest first so the derived ordering ranks them.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// Weakest first.
+    pub const ALL: [HandType; 7] = [
+        HandType::HighCard,
+        HandType::OnePair,
+        HandType::TwoPair,
+        HandType::ThreeOfAKind,
+        HandType::FullHouse,
+        HandType::FourOfAKind,
+        HandType::FiveOfAKind,
+    ];
+
+    /// Classifies a hand from how many times each label appears, in any order. Hands longer
+    /// than five cards take the type of their best five-card subset.
+    pub fn from_counts(counts: &[usize]) -> Self {
+        let mut counts = counts.to_vec();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        let first = counts.first().copied().unwrap_or(0);
+        let second = counts.get(1).copied().unwrap_or(0);
+
+        match (first, second) {
+            (5.., _) => Self::FiveOfAKind,
+            (4, _) => Self::FourOfAKind,
+            (3, 2..) => Self::FullHouse,
+            (3, _) => Self::ThreeOfAKind,
+            (2, 2) => Self::TwoPair,
+            (2, _) => Self::OnePair,
+            _ => Self::HighCard,
+        }
+    }
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::HighCard => "high card",
+            Self::OnePair => "one pair",
+            Self::TwoPair => "two pair",
+            Self::ThreeOfAKind => "three of a kind",
+            Self::FullHouse => "full house",
+            Self::FourOfAKind => "four of a kind",
+            Self::FiveOfAKind => "five of a kind",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Why a hand is not valid under a set of rules. Positions are 0-based.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HandError {
+    UnknownCard { position: usize, card: Card },
+    WrongSize { size: usize, expected: usize },
+}
+
+impl fmt::Display for HandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCard { position, card } => write!(f, "Card {} of the hand is '{}', not in the deck", position + 1, card),
+            Self::WrongSize { size, expected } => write!(f, "The hand has {} cards instead of {}", size, expected),
+        }
+    }
+}
+
+impl error::Error for HandError {}
+
+/// How cards compare and which of them are wild.
+pub trait Rules {
+    /// Strength of `card` when breaking ties, or `None` if the deck has no such card.
+    fn strength(&self, card: Card) -> Option<usize>;
+
+    /// Whether `card` stands in for any other card when picking the hand type.
+    fn is_wild(&self, card: Card) -> bool;
+
+    fn hand_size(&self) -> usize;
+
+    /// Card the wildcards of `cards` pretend to be: the most common other card, the strongest
+    /// one on a tie. `None` when the hand has no wildcards; an all-wild hand copies the strongest
+    /// card of the deck.
+    fn wild_target(&self, cards: &[Card]) -> Option<Card>;
+
+    /// Best type the hand can make once its wildcards are assigned.
+    fn hand_type(&self, cards: &[Card]) -> HandType {
+        let target = self.wild_target(cards);
+        let mut counts = HashMap::<Card, usize>::new();
+        for card in cards {
+            let card = if self.is_wild(*card) { target.unwrap() } else { *card };
+            *counts.entry(card).or_insert(0) += 1;
+        }
+        HandType::from_counts(&counts.into_values().collect::<Vec<_>>())
+    }
+
+    /// Checks `cards` against the rules and works out how the hand ranks.
+    fn evaluate(&self, cards: &[Card]) -> Result<Hand, HandError> {
+        let strengths = cards
+            .iter()
+            .enumerate()
+            .map(|(position, card)| self.strength(*card).ok_or(HandError::UnknownCard { position, card: *card }))
+            .collect::<Result<Vec<_>, _>>()?;
+        if cards.len() != self.hand_size() {
+            return Err(HandError::WrongSize {
+                size: cards.len(),
+                expected: self.hand_size(),
+            });
+        }
+
+        Ok(Hand {
+            hand_type: self.hand_type(cards),
+            strengths,
+            cards: cards.to_vec(),
+        })
+    }
+}
+
+/// Rules given as data: the deck from weakest to strongest card, the wildcards and the hand
+/// size.
+#[derive(Clone, Debug)]
+pub struct RuleSet {
+    order: Vec<Card>,
+    wildcards: Vec<Card>,
+    hand_size: usize,
+}
+
+impl RuleSet {
+    /// `order` lists the deck from weakest to strongest; every wildcard must be part of it.
+    pub fn new(order: &str, wildcards: &str, hand_size: usize) -> Self {
+        let order: Vec<Card> = order.chars().collect();
+        let wildcards: Vec<Card> = wildcards.chars().collect();
+        assert!(
+            wildcards.iter().all(|card| order.contains(card)),
+            "wildcards {:?} are not all in the deck {:?}",
+            wildcards,
+            order
+        );
+        Self { order, wildcards, hand_size }
+    }
+
+    /// Five cards, no wildcards, 2 weakest and A strongest.
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA", "", 5)
+    }
+
+    /// Like [`RuleSet::standard`], except J is wild and the weakest card.
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", "J", 5)
+    }
+
+    /// Every card, weakest first.
+    pub fn deck(&self) -> &[Card] {
+        &self.order
+    }
+}
+
+impl Rules for RuleSet {
+    fn strength(&self, card: Card) -> Option<usize> {
+        self.order.iter().position(|c| *c == card)
+    }
+
+    fn is_wild(&self, card: Card) -> bool {
+        self.wildcards.contains(&card)
+    }
+
+    fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    fn wild_target(&self, cards: &[Card]) -> Option<Card> {
+        if !cards.iter().any(|card| self.is_wild(*card)) {
+            return None;
+        }
+        let mut counts = HashMap::<Card, usize>::new();
+        for card in cards.iter().filter(|card| !self.is_wild(**card)) {
+            *counts.entry(*card).or_insert(0) += 1;
+        }
+        let best = counts
+            .into_iter()
+            .max_by_key(|(card, count)| (*count, self.strength(*card)))
+            .map(|(card, _)| card);
+        best.or_else(|| self.order.iter().rev().find(|card| !self.is_wild(**card)).copied())
+            .or_else(|| self.order.last().copied())
+    }
+}
+
+/// A hand evaluated under some [`Rules`]: hands compare by type, then card by card from the
+/// first. Only compare hands evaluated under the same rules.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hand {
+    hand_type: HandType,
+    strengths: Vec<usize>,
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.hand_type, &self.strengths).cmp(&(other.hand_type, &other.strengths))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.cards.iter().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(rules: &impl Rules, cards: &str) -> Hand {
+        rules.evaluate(&cards.chars().collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn classifies_every_type() {
+        let rules = RuleSet::standard();
+        let types: Vec<HandType> = ["23456", "A23A4", "23432", "TTT98", "23332", "AA8AA", "AAAAA"]
+            .iter()
+            .map(|cards| hand(&rules, cards).hand_type())
+            .collect();
+        assert_eq!(types, HandType::ALL);
+    }
+
+    #[test]
+    fn type_beats_cards_and_first_difference_breaks_ties() {
+        let rules = RuleSet::standard();
+        assert!(hand(&rules, "22345") > hand(&rules, "AKQJT"));
+        assert!(hand(&rules, "33332") > hand(&rules, "2AAAA"));
+        assert!(hand(&rules, "KK677") > hand(&rules, "KTJJT"));
+        assert_eq!(hand(&rules, "32T3K").cmp(&hand(&rules, "32T3K")), Ordering::Equal);
+    }
+
+    #[test]
+    fn jokers_are_wild_but_weakest() {
+        let rules = RuleSet::jokers();
+        assert_eq!(hand(&rules, "KTJJT").hand_type(), HandType::FourOfAKind);
+        assert_eq!(hand(&rules, "JJJJJ").hand_type(), HandType::FiveOfAKind);
+        assert_eq!(hand(&rules, "2233J").hand_type(), HandType::FullHouse);
+        assert!(hand(&rules, "JKKK2") < hand(&rules, "QQQQ2"));
+        assert!(hand(&rules, "JJJJJ") < hand(&rules, "2222J"));
+    }
+
+    #[test]
+    fn wildcards_copy_the_most_common_then_strongest_card() {
+        let rules = RuleSet::jokers();
+        let target = |cards: &str| rules.wild_target(&cards.chars().collect::<Vec<_>>());
+        assert_eq!(target("KTJJT"), Some('T'));
+        assert_eq!(target("2233J"), Some('3'));
+        assert_eq!(target("JJJJJ"), Some('A'));
+        assert_eq!(target("23456"), None);
+    }
+
+    #[test]
+    fn custom_rules() {
+        // Two wildcards and six-card hands.
+        let rules = RuleSet::new("2J3456789TQKA*", "J*", 6);
+        assert_eq!(hand(&rules, "J*2345").hand_type(), HandType::ThreeOfAKind);
+        assert_eq!(hand(&rules, "J*2233").hand_type(), HandType::FourOfAKind);
+        assert_eq!(hand(&rules, "2223JJ").hand_type(), HandType::FiveOfAKind);
+    }
+
+    #[test]
+    fn rejects_unknown_cards_and_wrong_sizes() {
+        let rules = RuleSet::standard();
+        assert_eq!(
+            rules.evaluate(&['2', '3', 'X', '4', '5']),
+            Err(HandError::UnknownCard { position: 2, card: 'X' })
+        );
+        assert_eq!(rules.evaluate(&['2', '3']), Err(HandError::WrongSize { size: 2, expected: 5 }));
+    }
+}
@@ -0,0 +1,141 @@
+/// Shape of an eventually periodic sequence `x0, f(x0), f(f(x0)), ...`: the first `start`
+/// values are a lead-in, then the next `length` values repeat forever.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the first value equal to the value at `index`.
+    pub fn first_occurrence(&self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+}
+
+/// Brent's algorithm: finds the cycle with about as few calls to `f` as possible, without
+/// storing the states it went through.
+pub fn brent<T: Clone + Eq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    // Find the length by moving the hare and teleporting the tortoise at powers of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, both meet where the cycle starts.
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Floyd's tortoise and hare; slower than [`brent`], kept as a cross-check.
+pub fn floyd<T: Clone + Eq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    let mut start = 0;
+    let mut tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Follows a table where every index points at another one, remembering every step.
+    fn naive(next: &[usize]) -> Cycle {
+        let mut seen = vec![None; next.len()];
+        let mut x = 0;
+        for i in 0.. {
+            if let Some(start) = seen[x] {
+                return Cycle { start, length: i - start };
+            }
+            seen[x] = Some(i);
+            x = next[x];
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn finds_lead_in_and_length() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2
+        let next = [1, 2, 3, 4, 2];
+        let expected = Cycle { start: 2, length: 3 };
+        assert_eq!(brent(0, |x: &usize| next[*x]), expected);
+        assert_eq!(floyd(0, |x: &usize| next[*x]), expected);
+    }
+
+    #[test]
+    fn fixed_point_and_pure_cycle() {
+        assert_eq!(brent(5, |x: &i32| *x), Cycle { start: 0, length: 1 });
+        assert_eq!(floyd(0, |x: &u8| (x + 1) % 7), Cycle { start: 0, length: 7 });
+    }
+
+    #[test]
+    fn agrees_with_naive_search() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for size in 1..40 {
+            let next: Vec<usize> = (0..size)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    (seed % size as u64) as usize
+                })
+                .collect();
+            let expected = naive(&next);
+            assert_eq!(brent(0, |x: &usize| next[*x]), expected);
+            assert_eq!(floyd(0, |x: &usize| next[*x]), expected);
+        }
+    }
+
+    #[test]
+    fn first_occurrence_folds_into_the_cycle() {
+        let cycle = Cycle { start: 2, length: 3 };
+        assert_eq!(cycle.first_occurrence(1), 1);
+        assert_eq!(cycle.first_occurrence(4), 4);
+        assert_eq!(cycle.first_occurrence(5), 2);
+        assert_eq!(cycle.first_occurrence(13), 4);
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+
+use crate::{
+    error::ParseError,
+    solution::Day,
+};
+
+const DAY: u8 = 1;
+
+pub struct Day01;
+
+impl Day for Day01 {
+    const DAY: u8 = DAY;
+    type Input = Document;
+
+    fn parse(input: &str) -> Result<Document> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Document) -> Result<impl Display> {
+        Ok(part1(input)?)
+    }
+
+    fn part2(input: &Document) -> Result<impl Display> {
+        Ok(part2(input)?)
+    }
+}
+
+/// The calibration document, one entry per line. Which characters count as digits depends on the
+/// part, so lines are only checked once a part reads them.
+#[derive(Clone, Debug)]
+pub struct Document {
+    pub lines: Vec<String>,
+}
+
+pub fn parse_input(input: &str) -> Document {
+    Document {
+        lines: input.lines().map(String::from).collect(),
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u32, ParseError> {
+    part1(&parse_input(input))
+}
+
+pub fn solve_part2(input: &str) -> Result<u32, ParseError> {
+    part2(&parse_input(input))
+}
+
+/// Sum of the calibration values made of the first and last numeric digit of each line.
+pub fn part1(document: &Document) -> Result<u32, ParseError> {
+    let mut result = 0;
+
+    for (i, line) in document.lines.iter().enumerate() {
+        let first_digit = line
+            .chars()
+            .find_map(|c| c.to_digit(10))
+            .ok_or_else(|| missing_digit(i, line))?;
+        let last_digit = line
+            .chars()
+            .rev()
+            .find_map(|c| c.to_digit(10))
+            .ok_or_else(|| missing_digit(i, line))?;
+        result += first_digit * 10 + last_digit;
+    }
+
+    Ok(result)
+}
+
+/// Like [`part1`], but spelled out digits ("one" to "nine") count too.
+pub fn part2(document: &Document) -> Result<u32, ParseError> {
+    let mut result = 0;
+
+    for (i, line) in document.lines.iter().enumerate() {
+        let first = find_digit_left(line).ok_or_else(|| missing_digit(i, line))?;
+        let last = find_digit_right(line).ok_or_else(|| missing_digit(i, line))?;
+
+        result += first * 10 + last;
+    }
+
+    Ok(result)
+}
+
+fn missing_digit(idx: usize, line: &str) -> ParseError {
+    ParseError::at(DAY, idx + 1, line, "", "a digit somewhere in the line")
+}
+
+/// First digit in `input`, numeric or spelled out.
+pub fn find_digit_left(input: &str) -> Option<u32> {
+    input.char_indices().find_map(|(i, _)| to_digit(&input[i..]))
+}
+
+/// Last digit in `input`, numeric or spelled out. Overlapping words such as "eightwo" end in the
+/// later one.
+pub fn find_digit_right(input: &str) -> Option<u32> {
+    input.char_indices().rev().find_map(|(i, _)| to_digit(&input[i..]))
+}
+
+const PATTERNS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn to_digit(input: &str) -> Option<u32> {
+    if let Some(first_char) = input.chars().next() {
+        if let Some(n) = first_char.to_digit(10) {
+            return Some(n);
+        }
+    }
+
+    for (i, pat) in PATTERNS.iter().enumerate() {
+        if input.starts_with(pat) {
+            return Some(i as u32);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const EXAMPLE_2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE_1).unwrap(), 142);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE_2).unwrap(), 281);
+    }
+
+    #[test]
+    fn overlapping_words_count_for_both_ends() {
+        assert_eq!(solve_part2("eightwo").unwrap(), 82);
+        assert_eq!(solve_part2("oneight").unwrap(), 18);
+        assert_eq!(solve_part2("twone3").unwrap(), 23);
+    }
+
+    #[test]
+    fn single_digit_is_first_and_last() {
+        assert_eq!(solve_part1("treb7uchet").unwrap(), 77);
+        assert_eq!(solve_part2("seven").unwrap(), 77);
+    }
+
+    #[test]
+    fn words_do_not_count_in_part1() {
+        let err = solve_part1("1abc\nnine").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+    }
+
+    #[test]
+    fn non_ascii_text_is_skipped() {
+        assert_eq!(solve_part2("é1\nñeightwo→").unwrap(), 11 + 82);
+        assert_eq!(solve_part1("x7é").unwrap(), 77);
+        let err = solve_part2("1\néé").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+}
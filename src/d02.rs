@@ -1,41 +1,127 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
 
 use anyhow::Result;
 
+use crate::{
+    error::ParseError,
+    solution::Day,
+};
+
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, line_ending, space1, u32},
-    combinator::map_res,
+    character::complete::{alpha1, space1, u32},
+    combinator::{cut, eof, map_res},
+    error::{context, VerboseError},
     multi::separated_list1,
-    sequence::{delimited, tuple},
-    IResult,
+    sequence::{delimited, terminated, tuple},
 };
 
-pub fn parse_games(input: &str) -> Result<Vec<Game>, ()> {
-    let mut parser = separated_list1(line_ending, game);
-    if let Ok((_, games)) = parser(input) {
-        Ok(games)
-    } else {
-        Err(())
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+const MAX_RED: u32 = 12;
+const MAX_GREEN: u32 = 13;
+const MAX_BLUE: u32 = 14;
+
+const DAY: u8 = 2;
+
+pub struct Day02;
+
+impl Day for Day02 {
+    const DAY: u8 = DAY;
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Vec<Game>> {
+        Ok(parse_games(input)?)
+    }
+
+    fn part1(input: &Vec<Game>) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Vec<Game>) -> Result<impl Display> {
+        Ok(part2(input))
     }
 }
 
-fn game(input: &str) -> IResult<&str, Game> {
+pub fn solve_part1(input: &str) -> Result<u32, ParseError> {
+    Ok(part1(&parse_games(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u32, ParseError> {
+    Ok(part2(&parse_games(input)?))
+}
+
+/// Sum of the ids of the games possible with 12 red, 13 green and 14 blue cubes.
+pub fn part1(games: &[Game]) -> u32 {
+    games.iter()
+        .filter(|g| is_game_valid(g))
+        .map(|g| g.id)
+        .sum()
+}
+
+/// Sum of the powers of the smallest set of cubes that makes each game possible.
+pub fn part2(games: &[Game]) -> u32 {
+    games.iter()
+        .map(game_minimums)
+        .map(|(red, green, blue)| red * green * blue)
+        .sum()
+}
+
+pub fn is_game_valid(game: &Game) -> bool {
+    !game.subsets.iter().any(|s| !is_subset_valid(s))
+}
+
+fn is_subset_valid(subset: &Subset) -> bool {
+    subset.get(&CubeColor::Red).map_or(0, |v| *v) <= MAX_RED
+        && subset.get(&CubeColor::Green).map_or(0, |v| *v) <= MAX_GREEN
+        && subset.get(&CubeColor::Blue).map_or(0, |v| *v) <= MAX_BLUE
+}
+
+/// Red, Green, Blue
+pub fn game_minimums(game: &Game) -> (u32, u32, u32) {
+    game.subsets.iter().fold((0, 0, 0), subset_reducer)
+}
+
+fn subset_reducer((r, g, b): (u32, u32, u32), subset: &Subset) -> (u32, u32, u32) {
+    let sr = subset.get(&CubeColor::Red).map_or(0, |v| *v);
+    let sg = subset.get(&CubeColor::Green).map_or(0, |v| *v);
+    let sb = subset.get(&CubeColor::Blue).map_or(0, |v| *v);
+
+    (sr.max(r), sg.max(g), sb.max(b))
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            terminated(game, context("end of line", eof))(line)
+                .map(|(_, game)| game)
+                .map_err(|err| ParseError::from_nom(DAY, i + 1, line, err))
+        })
+        .collect()
+}
+
+fn game(input: &str) -> IResult<'_, Game> {
     let (input, id) = game_id(input)?;
     let (input, subsets) = game_subsets(input)?;
     Ok((input, Game { id, subsets }))
 }
 
-fn game_id(input: &str) -> IResult<&str, u32> {
-    delimited(tag("Game "), u32, tag(": "))(input)
+fn game_id(input: &str) -> IResult<'_, u32> {
+    delimited(
+        context("'Game '", tag("Game ")),
+        context("a game id", u32),
+        context("': '", tag(": ")),
+    )(input)
 }
 
-fn game_subsets(input: &str) -> IResult<&str, Vec<Subset>> {
-    separated_list1(tag("; "), subset)(input)
+fn game_subsets(input: &str) -> IResult<'_, Vec<Subset>> {
+    separated_list1(tag("; "), cut(subset))(input)
 }
 
-fn subset(input: &str) -> IResult<&str, Subset> {
-    let (input, amounts) = separated_list1(tag(", "), cube_amount)(input)?;
+fn subset(input: &str) -> IResult<'_, Subset> {
+    let (input, amounts) = separated_list1(tag(", "), cut(cube_amount))(input)?;
     
     let mut subset = Subset::new();
     for (color, amount) in amounts.iter() {
@@ -45,12 +131,16 @@ fn subset(input: &str) -> IResult<&str, Subset> {
     Ok((input, subset))
 }
 
-fn cube_amount(input: &str) -> IResult<&str, (CubeColor, u32)> {
-    let (input, (amount, _, color)) = tuple((u32, space1, color_name))(input)?;
+fn cube_amount(input: &str) -> IResult<'_, (CubeColor, u32)> {
+    let (input, (amount, _, color)) = tuple((
+        context("a cube count", u32),
+        context("a space", space1),
+        context("a colour (red, green or blue)", color_name),
+    ))(input)?;
     Ok((input, (color, amount)))
 }
 
-fn color_name(input: &str) -> IResult<&str, CubeColor> {
+fn color_name(input: &str) -> IResult<'_, CubeColor> {
     map_res(alpha1, CubeColor::from_str)(input)
 }
 
@@ -81,3 +171,43 @@ impl FromStr for CubeColor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 8);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), 2286);
+    }
+
+    #[test]
+    fn limits_are_inclusive() {
+        assert_eq!(solve_part1("Game 7: 12 red, 13 green, 14 blue").unwrap(), 7);
+        assert_eq!(solve_part1("Game 7: 12 red, 14 green; 1 blue").unwrap(), 0);
+    }
+
+    #[test]
+    fn missing_colours_count_as_zero() {
+        assert_eq!(solve_part2("Game 1: 3 red; 2 red").unwrap(), 0);
+    }
+
+    #[test]
+    fn unknown_colour_is_rejected() {
+        let err = solve_part1("Game 1: 3 red, 2 purple").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 18));
+    }
+}
@@ -0,0 +1,249 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use anyhow::Result;
+
+use crate::{
+    error::ParseError,
+    geometry::{Direction, Point},
+    grid::Grid,
+    render::{Colour, Render},
+    solution::Day,
+};
+
+const DAY: u8 = 3;
+
+pub struct Day03;
+
+impl Day for Day03 {
+    const DAY: u8 = DAY;
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Schematic> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Schematic) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Schematic) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+/// The engine schematic and the numbers found in it, left to right and top to bottom.
+#[derive(Debug)]
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub numbers: Vec<NumberPos>,
+}
+
+#[derive(Debug)]
+pub struct NumberPos {
+    pub value: u64,
+    /// Position of the first digit.
+    pub start: Point,
+    pub digits: i64,
+}
+
+impl NumberPos {
+    /// Positions of the number's digits.
+    pub fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.digits).map(|x| self.start + Direction::East.offset() * x)
+    }
+}
+
+/// Anything other than a digit or a period.
+pub fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, ParseError> {
+    Ok(part1(&parse_input(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, ParseError> {
+    Ok(part2(&parse_input(input)?))
+}
+
+/// Sum of every number adjacent to a symbol, diagonals included.
+pub fn part1(schematic: &Schematic) -> u64 {
+    schematic
+        .numbers
+        .iter()
+        .filter(|num| schematic.is_part(num))
+        .map(|num| num.value)
+        .sum()
+}
+
+/// Sum of the gear ratios: the product of the two numbers next to a '*' with exactly two.
+pub fn part2(schematic: &Schematic) -> u64 {
+    schematic
+        .gears()
+        .iter()
+        .map(|(_, [a, b])| schematic.numbers[*a].value * schematic.numbers[*b].value)
+        .sum()
+}
+
+impl Schematic {
+    /// Whether a symbol touches the number, diagonals included.
+    pub fn is_part(&self, num: &NumberPos) -> bool {
+        num.cells()
+            .flat_map(|pos| self.grid.neighbours8(pos))
+            .any(|pos| is_symbol(self.grid[pos]))
+    }
+
+    /// Every '*' next to exactly two numbers, with the indices of those numbers.
+    pub fn gears(&self) -> Vec<(Point, [usize; 2])> {
+        let mut owners: Grid<Option<usize>> = self.grid.map(|_| None);
+        for (i, num) in self.numbers.iter().enumerate() {
+            for pos in num.cells() {
+                owners[pos] = Some(i);
+            }
+        }
+
+        self.grid
+            .iter()
+            .filter(|(_, c)| **c == '*')
+            .filter_map(|(pos, _)| {
+                let adjacent: BTreeSet<usize> = self.grid.neighbours8(pos).filter_map(|n| owners[n]).collect();
+                match adjacent.into_iter().collect::<Vec<_>>().as_slice() {
+                    [a, b] => Some((pos, [*a, *b])),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The schematic with part numbers and gears picked out: parts in green, other numbers in grey,
+/// gears in yellow and the other symbols in cyan.
+pub struct SchematicView<'a> {
+    schematic: &'a Schematic,
+    colours: Grid<Option<Colour>>,
+}
+
+impl<'a> SchematicView<'a> {
+    pub fn new(schematic: &'a Schematic) -> Self {
+        let grid = &schematic.grid;
+        let mut colours = grid.map(|c| is_symbol(*c).then_some(Colour::Cyan));
+        for num in &schematic.numbers {
+            let colour = if schematic.is_part(num) { Colour::Green } else { Colour::Grey };
+            for pos in num.cells() {
+                colours[pos] = Some(colour);
+            }
+        }
+        for (pos, _) in schematic.gears() {
+            colours[pos] = Some(Colour::Yellow);
+        }
+        Self { schematic, colours }
+    }
+}
+
+impl Render for SchematicView<'_> {
+    type Cell = char;
+
+    fn grid(&self) -> &Grid<char> {
+        &self.schematic.grid
+    }
+
+    fn glyph(&self, _pos: Point, cell: &char) -> char {
+        *cell
+    }
+
+    fn colour(&self, pos: Point, _cell: &char) -> Option<Colour> {
+        self.colours[pos]
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Schematic, ParseError> {
+    let grid = Grid::parse(input, Some).map_err(|err| err.to_parse_error(DAY, input, "a cell"))?;
+    let numbers = find_numbers(&grid)?;
+    Ok(Schematic { grid, numbers })
+}
+
+fn find_numbers(grid: &Grid<char>) -> Result<Vec<NumberPos>, ParseError> {
+    let mut numbers = vec![];
+
+    for (row, cells) in grid.iter_rows().enumerate() {
+        let mut column = 0;
+        while column < cells.len() {
+            let digits = cells[column..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if digits == 0 {
+                column += 1;
+                continue;
+            }
+
+            let value = cells[column..column + digits]
+                .iter()
+                .try_fold(0u64, |acc, d| acc.checked_mul(10)?.checked_add(d.to_digit(10)? as u64))
+                .ok_or_else(|| ParseError::new(DAY, row + 1, column + 1, "a number that fits in 64 bits"))?;
+            numbers.push(NumberPos {
+                value,
+                start: Point::new(row as i64, column as i64),
+                digits: digits as i64,
+            });
+            column += digits;
+        }
+    }
+
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Mode;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 4361);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), 467835);
+    }
+
+    #[test]
+    fn numbers_touching_only_diagonally_are_parts() {
+        assert_eq!(solve_part1("12...\n..#..\n...34").unwrap(), 46);
+        assert_eq!(solve_part1("12...\n...#.\n.....").unwrap(), 0);
+    }
+
+    #[test]
+    fn gears_need_exactly_two_numbers() {
+        assert_eq!(solve_part2("2*3*4").unwrap(), 18);
+        assert_eq!(solve_part2("2.3\n.*.\n.4.").unwrap(), 0);
+    }
+
+    #[test]
+    fn numbers_past_64_bits_are_rejected() {
+        assert_eq!(solve_part1("18446744073709551615*").unwrap(), u64::MAX);
+        let err = parse_input(".18446744073709551616*").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+    }
+
+    #[test]
+    fn view_colours_parts_and_gears() {
+        let schematic = parse_input("12*3.4\n..#...").unwrap();
+        let view = SchematicView::new(&schematic);
+        let colours: Vec<Option<Colour>> = schematic.grid.positions().map(|pos| view.colour(pos, &'.')).collect();
+        let (g, y, c) = (Some(Colour::Green), Some(Colour::Yellow), Some(Colour::Cyan));
+        assert_eq!(colours, [g, g, y, g, None, Some(Colour::Grey), None, None, c, None, None, None]);
+        assert_eq!(view.render(Mode::Plain), "12*3.4\n..#...\n");
+    }
+}
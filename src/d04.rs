@@ -0,0 +1,168 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    error::ParseError,
+    solution::Day,
+};
+
+const DAY: u8 = 4;
+
+pub struct Day04;
+
+impl Day for Day04 {
+    const DAY: u8 = DAY;
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Vec<Card>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<Card>) -> Result<impl Display> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Card>) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+/// A scratchcard's winning numbers (left of the '|') and the numbers on it (right of it).
+#[derive(Debug)]
+pub struct Card {
+    pub winners: HashSet<u32>,
+    pub numbers: HashSet<u32>,
+}
+
+impl Card {
+    pub fn win_count(&self) -> usize {
+        self.winners.intersection(&self.numbers).count()
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<usize> {
+    part1(&parse_input(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<usize, ParseError> {
+    Ok(part2(&parse_input(input)?))
+}
+
+/// Total points: each card is worth one point for its first match, doubled for every other one.
+pub fn part1(cards: &[Card]) -> Result<usize> {
+    cards.iter().enumerate().try_fold(0usize, |total, (i, card)| {
+        real_score(card.win_count())
+            .and_then(|score| total.checked_add(score))
+            .ok_or_else(|| anyhow!("Card {} takes the points past {}", i + 1, usize::MAX))
+    })
+}
+
+/// Total scratchcards once every card has won copies of the cards below it.
+pub fn part2(cards: &[Card]) -> usize {
+    let mut copies = HashMap::<usize, usize>::new();
+
+    let mut result: usize = 0;
+    for (i, card) in cards.iter().enumerate() {
+        result += card_copies(i, card, &mut copies);
+    }
+
+    result
+}
+
+fn card_copies(idx: usize, card: &Card, copies: &mut HashMap<usize, usize>) -> usize {
+    let win_count = card.win_count();
+    let current_copies = copies.get(&idx).map_or(1, |v| *v);
+
+    for i in 1..=win_count {
+        let cidx = idx + i;
+        let n = copies.get(&cidx).map_or(1, |v| *v);
+        copies.insert(cidx, n + current_copies);
+    }
+
+    current_copies
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Card>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_card(i + 1, line))
+        .collect()
+}
+
+fn parse_card(line_no: usize, line: &str) -> Result<Card, ParseError> {
+    let (_, numbers) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at(DAY, line_no, line, "", "':' after the card id"))?;
+    let (left, right) = numbers
+        .split_once('|')
+        .ok_or_else(|| ParseError::at(DAY, line_no, line, "", "'|' between the number lists"))?;
+
+    Ok(Card {
+        winners: parse_numbers_list(line_no, line, left)?,
+        numbers: parse_numbers_list(line_no, line, right)?,
+    })
+}
+
+fn parse_numbers_list(line_no: usize, line: &str, list: &str) -> Result<HashSet<u32>, ParseError> {
+    list.split_whitespace()
+        .map(|s| {
+            s.parse::<u32>()
+                .map_err(|_| ParseError::at_token(DAY, line_no, line, s, "a number"))
+        })
+        .collect()
+}
+
+/// Points for `wins` matches, or `None` if they do not fit in a `usize`.
+fn real_score(wins: usize) -> Option<usize> {
+    if wins > 0 {
+        u32::try_from(wins - 1).ok().and_then(|shift| 1usize.checked_shl(shift))
+    } else {
+        Some(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), 30);
+    }
+
+    #[test]
+    fn card_without_matches_scores_nothing() {
+        assert_eq!(solve_part1("Card 1: 1 2 | 3 4").unwrap(), 0);
+        assert_eq!(solve_part2("Card 1: 1 2 | 3 4").unwrap(), 1);
+    }
+
+    #[test]
+    fn points_that_overflow_are_an_error() {
+        let card = |matches: u32| Card {
+            winners: (1..=matches).collect(),
+            numbers: (1..=matches).collect(),
+        };
+        assert_eq!(part1(&[card(usize::BITS)]).unwrap(), 1 << (usize::BITS - 1));
+        assert!(part1(&[card(usize::BITS + 1)]).is_err());
+        assert!(part1(&[card(usize::BITS), card(usize::BITS)]).is_err());
+    }
+}
@@ -1,17 +1,63 @@
-use core::fmt;
-use std::{iter::Peekable, str::Lines};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    iter::{Enumerate, Peekable},
+    str,
+};
+
+use anyhow::Result;
+
+use crate::{
+    error::ParseError,
+    interval::{Interval, IntervalSet, PiecewiseMap},
+    solution::Day,
+};
+
+type Lines<'a> = Peekable<Enumerate<str::Lines<'a>>>;
+
+const DAY: u8 = 5;
+
+const SEED: &str = "seed";
+const LOCATION: &str = "location";
+
+pub struct Day05;
+
+impl Day for Day05 {
+    const DAY: u8 = DAY;
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Almanac> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Almanac) -> Result<impl Display> {
+        Ok(part1(input)?)
+    }
+
+    fn part2(input: &Almanac) -> Result<impl Display> {
+        Ok(part2(input)?)
+    }
+}
 
 #[derive(Debug)]
 pub struct Almanac {
-    pub seeds: Vec<i64>,
-    pub seed_ranges: Vec<IdRange>,
-    pub maps: Vec<TypeMap>,
+    /// Never empty, the parser checks it.
+    seeds: Vec<i64>,
+    /// The seeds read as pairs of start and length, or why they cannot be.
+    seed_ranges: Result<IntervalSet, ParseError>,
+    /// Maps keyed by their source category. Each category is the source of at most one map, so
+    /// following them from any category is unambiguous.
+    pub maps: HashMap<String, CategoryMap>,
+    /// Lines in the input, so that a missing map is reported after the last of them.
+    lines: usize,
 }
 
-#[derive(Clone, Copy)]
-pub struct IdRange {
-    pub start: i64,
-    pub end: i64,
+/// A map such as "seed-to-soil", translating ids of `source` into ids of `dest`.
+#[derive(Debug)]
+pub struct CategoryMap {
+    pub source: String,
+    pub dest: String,
+    pub mappings: TypeMap,
 }
 
 pub type TypeMap = Vec<Mapping>;
@@ -23,94 +69,473 @@ pub struct Mapping {
     pub length: i64,
 }
 
-impl fmt::Display for IdRange {
+impl Mapping {
+    pub fn source_range(&self) -> Interval {
+        Interval::from_len(self.source, self.length)
+    }
 
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}-{}", self.start, self.end)
+    pub fn offset(&self) -> i64 {
+        self.dest - self.source
     }
+}
+
+pub fn solve_part1(input: &str) -> Result<i64, ParseError> {
+    part1(&parse_input(input)?)
+}
 
+pub fn solve_part2(input: &str) -> Result<i64, ParseError> {
+    part2(&parse_input(input)?)
 }
 
-impl fmt::Debug for IdRange {
+/// Lowest location number of any of the listed seeds.
+pub fn part1(almanac: &Almanac) -> Result<i64, ParseError> {
+    let seed_to_location = almanac.seed_to_location()?;
+
+    Ok(almanac
+        .seeds
+        .iter()
+        .map(|id| seed_to_location.apply(*id))
+        .fold(i64::MAX, i64::min))
+}
+
+/// Lowest location number when the seed list is read as pairs of start and length.
+pub fn part2(almanac: &Almanac) -> Result<i64, ParseError> {
+    lowest_location(almanac, almanac.seed_ranges()?)?
+        .map(|(_, location)| location)
+        .ok_or_else(|| ParseError::new(DAY, 1, 1, "at least one seed range"))
+}
 
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}-{}", self.start, self.end)
+/// The seed among `seeds` with the lowest location, and that location.
+pub fn lowest_location(almanac: &Almanac, seeds: &IntervalSet) -> Result<Option<(i64, i64)>, ParseError> {
+    Ok(almanac.seed_to_location()?.min_image(seeds))
+}
+
+impl Almanac {
+    pub fn seeds(&self) -> &[i64] {
+        &self.seeds
+    }
+
+    /// The seed list read as pairs of start and length. Fails when the last seed has no length.
+    pub fn seed_ranges(&self) -> Result<&IntervalSet, ParseError> {
+        self.seed_ranges.as_ref().map_err(Clone::clone)
+    }
+
+    /// The maps leading from category `from` to category `to`, in the order they apply.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&CategoryMap>, ParseError> {
+        let mut chain = vec![];
+        let mut category = from;
+        let mut visited = HashSet::new();
+        while category != to {
+            if !visited.insert(category) {
+                return Err(self.missing(format!(
+                    "a chain of maps from '{}' to '{}', not a cycle through '{}'",
+                    from, to, category
+                )));
+            }
+            let map = self
+                .maps
+                .get(category)
+                .ok_or_else(|| self.missing(format!("a map from '{}'", category)))?;
+            chain.push(map);
+            category = &map.dest;
+        }
+        Ok(chain)
+    }
+
+    /// The maps from category `from` to category `to` composed into a single function.
+    pub fn translation(&self, from: &str, to: &str) -> Result<PiecewiseMap, ParseError> {
+        let chain = self.chain(from, to)?;
+        Ok(
+            chain
+                .iter()
+                .fold(PiecewiseMap::identity(), |composed, map| composed.then(&piecewise(&map.mappings))),
+        )
+    }
+
+    /// Every map from seed to location composed into a single function.
+    pub fn seed_to_location(&self) -> Result<PiecewiseMap, ParseError> {
+        self.translation(SEED, LOCATION)
+    }
+
+    /// Inverse of [`Almanac::seed_to_location`], if no two seeds share a location.
+    pub fn location_to_seed(&self) -> Result<Option<PiecewiseMap>, ParseError> {
+        Ok(self.seed_to_location()?.inverse())
+    }
+
+    /// Error for a map the almanac lacks, which would have come after its last line.
+    fn missing(&self, expected: String) -> ParseError {
+        ParseError::new(DAY, self.lines + 1, 1, expected)
     }
 }
 
-pub fn parse_input(input: &str) -> Almanac {
-    let mut lines = input.lines().peekable();
-    let seeds = parse_seed_list(&mut lines);
+/// A single map as a function. Like [`apply_map`], the first matching mapping wins.
+pub fn piecewise(type_map: &TypeMap) -> PiecewiseMap {
+    let pieces: Vec<(Interval, i64)> = type_map
+        .iter()
+        .map(|mapping| (mapping.source_range(), mapping.offset()))
+        .collect();
+    PiecewiseMap::from_pieces(&pieces)
+}
 
-    let seed_ranges = into_ranges(&seeds);
+/// Follows `id` through every map, from seed to location.
+pub fn translate_id(mut id: i64, almanac: &Almanac) -> Result<i64, ParseError> {
+    for map in almanac.chain(SEED, LOCATION)? {
+        id = apply_map(id, &map.mappings);
+    }
+    Ok(id)
+}
 
-    let maps = parse_maps(&mut lines);
+pub fn apply_map(id: i64, map: &TypeMap) -> i64 {
+    for mapping in map.iter() {
+        if let Some(new_id) = apply_mapping(id, mapping) {
+            return new_id;
+        }
+    }
+    id
+}
 
-    Almanac {
+fn apply_mapping(id: i64, mapping: &Mapping) -> Option<i64> {
+    if id >= mapping.source && id < mapping.source + mapping.length {
+        Some(id + (mapping.dest - mapping.source))
+    } else {
+        None
+    }
+}
+
+/// Translates a set of ranges through one map, splitting them where mappings start or end.
+pub fn map_ranges(type_map: &TypeMap, ranges: &IntervalSet) -> IntervalSet {
+    let pieces: Vec<(Interval, i64)> = type_map
+        .iter()
+        .map(|mapping| (mapping.source_range(), mapping.offset()))
+        .collect();
+    ranges.map_piecewise(&pieces)
+}
+
+pub fn parse_input(input: &str) -> Result<Almanac, ParseError> {
+    let mut lines = input.lines().enumerate().peekable();
+    let (seeds, seed_ranges) = parse_seed_list(input, &mut lines)?;
+
+    let maps = parse_maps(&mut lines)?;
+    let almanac = Almanac {
         seeds,
         seed_ranges,
         maps,
-    }
+        lines: input.lines().count(),
+    };
+    // Seeds must lead to locations one map after another.
+    almanac.chain(SEED, LOCATION)?;
+
+    Ok(almanac)
 }
 
-fn parse_seed_list(lines: &mut Peekable<Lines>) -> Vec<i64> {
-    let line = lines.next().expect("Could not read seeds line");
+/// The seeds, and the same numbers as ranges for part 2.
+fn parse_seed_list(input: &str, lines: &mut Lines) -> Result<(Vec<i64>, Result<IntervalSet, ParseError>), ParseError> {
+    let (idx, line) = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(DAY, input, "a 'seeds:' line"))?;
 
-    let (_, seed_list) = line.split_once(':').expect("Could not split seeds at ':'");
+    let (_, seed_list) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at(DAY, idx + 1, line, line, "'seeds:'"))?;
 
-    let seeds: Vec<i64> = seed_list
+    let seeds = seed_list
         .split_whitespace()
-        .map(|v| v.parse::<i64>().expect("Could not parse seed number"))
-        .collect();
+        .map(|v| {
+            v.parse::<i64>()
+                .map_err(|_| ParseError::at_token(DAY, idx + 1, line, v, "a seed number"))
+        })
+        .collect::<Result<Vec<i64>, ParseError>>()?;
+    if seeds.is_empty() {
+        return Err(ParseError::at(DAY, idx + 1, line, "", "a seed number"));
+    }
+    // Only part 2 needs the seeds to pair up, so an odd count fails there.
+    let seed_ranges = if seeds.len() % 2 == 0 {
+        Ok(into_ranges(&seeds))
+    } else {
+        Err(ParseError::at(DAY, idx + 1, line, "", "a length after the last seed"))
+    };
 
-    lines.next(); // Discard empty line
-    seeds
+    if let Some((idx, line)) = lines.next() {
+        if !line.is_empty() {
+            return Err(ParseError::at(DAY, idx + 1, line, line, "an empty line after the seeds"));
+        }
+    }
+    Ok((seeds, seed_ranges))
 }
 
-fn parse_maps(lines: &mut Peekable<Lines>) -> Vec<TypeMap> {
-    let mut maps = vec![];
-    while let Some(_) = lines.peek() {
-        let map = parse_single_map(lines);
-        maps.push(map);
+fn parse_maps(lines: &mut Lines) -> Result<HashMap<String, CategoryMap>, ParseError> {
+    let mut maps = HashMap::new();
+    while let Some((idx, header)) = lines.next() {
+        let (source, dest) = parse_header(idx + 1, header)?;
+        if maps.contains_key(&source) {
+            return Err(ParseError::at(DAY, idx + 1, header, header, format!("a single map from '{}'", source)));
+        }
+
+        let mappings = parse_single_map(lines)?;
+        maps.insert(source.clone(), CategoryMap { source, dest, mappings });
     }
-    maps
+    Ok(maps)
 }
 
-fn parse_single_map(lines: &mut Peekable<Lines>) -> TypeMap {
-    let mut mappings = TypeMap::new();
-
-    if let Some(line) = lines.next() {
-        if !line.ends_with(":") {
-            panic!("Parsing map header, line does not end with ':'");
-        }
+/// Reads "<source>-to-<dest> map:".
+fn parse_header(line_no: usize, line: &str) -> Result<(String, String), ParseError> {
+    let expected = "a map header like 'seed-to-soil map:'";
+    let (source, dest) = line
+        .strip_suffix(" map:")
+        .and_then(|names| names.split_once("-to-"))
+        .ok_or_else(|| ParseError::at(DAY, line_no, line, line, expected))?;
+    if source.is_empty() || dest.is_empty() {
+        return Err(ParseError::at(DAY, line_no, line, line, expected));
     }
+    Ok((source.to_string(), dest.to_string()))
+}
+
+/// Mappings up to the next empty line. Their source ranges must not overlap.
+fn parse_single_map(lines: &mut Lines) -> Result<TypeMap, ParseError> {
+    let mut mappings = TypeMap::new();
+    let mut line_numbers = vec![];
 
-    while let Some(line) = lines.next() {
+    for (idx, line) in lines.by_ref() {
         if line.is_empty() {
             break;
         }
-        let mapping = parse_mapping(line);
+        let mapping = parse_mapping(idx + 1, line)?;
+
+        let overlap = mappings
+            .iter()
+            .position(|other: &Mapping| other.source_range().intersection(&mapping.source_range()).is_some());
+        if let Some(other) = overlap {
+            let source = line.split_whitespace().nth(1).unwrap_or(line);
+            return Err(ParseError::at_token(
+                DAY,
+                idx + 1,
+                line,
+                source,
+                format!("a source range not overlapping the mapping on line {}", line_numbers[other]),
+            ));
+        }
+
         mappings.push(mapping);
+        line_numbers.push(idx + 1);
+    }
+
+    Ok(mappings)
+}
+
+fn parse_mapping(line_no: usize, line: &str) -> Result<Mapping, ParseError> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if let Some(extra) = tokens.get(3) {
+        return Err(ParseError::at_token(DAY, line_no, line, extra, "end of line after three numbers"));
     }
 
-    mappings
+    let number = |idx: usize, name: &str| {
+        let token = tokens
+            .get(idx)
+            .ok_or_else(|| ParseError::at(DAY, line_no, line, "", format!("the mapping {}", name)))?;
+        token
+            .parse::<i64>()
+            .map_err(|_| ParseError::at_token(DAY, line_no, line, token, format!("the mapping {} as a number", name)))
+    };
+    let dest = number(0, "destination")?;
+    let source = number(1, "source")?;
+    let length = number(2, "length")?;
+    Ok(Mapping { source, dest, length })
 }
 
-fn parse_mapping(line: &str) -> Mapping {
-    let mut parts = line.split_whitespace().map(|v| v.parse::<i64>().expect("Could not parse mapping number"));
-    let dest = parts.next().expect("Could not get mapping destination");
-    let source = parts.next().expect("Could not get mapping source");
-    let length = parts.next().expect("Could not get mapping length");
-    Mapping { source, dest, length }
+/// Pairs of start and length, for an even number of seeds.
+fn into_ranges(seeds: &[i64]) -> IntervalSet {
+    seeds
+        .chunks_exact(2)
+        .map(|pair| Interval::from_len(pair[0], pair[1]))
+        .collect()
 }
 
-fn into_ranges(seeds: &Vec<i64>) -> Vec<IdRange> {
-    let mut iter = seeds.iter();
-    let mut ranges = vec![];
-    while let Some(start) = iter.next() {
-        if let Some(len) = iter.next() {
-            ranges.push(IdRange { start: *start, end: *start + *len });
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    /// Maps [10, 20) down to [0, 10) and leaves everything else alone.
+    const BOUNDARIES: &str = "\
+seeds: 5 5 20 5
+
+seed-to-location map:
+0 10 10
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 35);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), 46);
+    }
+
+    #[test]
+    fn composed_map_matches_map_by_map_translation() {
+        let almanac = parse_input(EXAMPLE).unwrap();
+        let seed_to_location = almanac.seed_to_location().unwrap();
+        let location_to_seed = almanac.location_to_seed().unwrap().unwrap();
+        for seed in 0..120 {
+            let location = translate_id(seed, &almanac).unwrap();
+            assert_eq!(seed_to_location.apply(seed), location);
+            assert_eq!(location_to_seed.apply(location), seed);
         }
     }
-    ranges
+
+    #[test]
+    fn lowest_location_names_its_seed() {
+        let almanac = parse_input(EXAMPLE).unwrap();
+        assert_eq!(lowest_location(&almanac, almanac.seed_ranges().unwrap()), Ok(Some((82, 46))));
+    }
+
+    #[test]
+    fn fold_of_range_maps_agrees_with_composed_map() {
+        let almanac = parse_input(EXAMPLE).unwrap();
+        let folded = almanac
+            .chain("seed", "location")
+            .unwrap()
+            .iter()
+            .fold(almanac.seed_ranges().unwrap().clone(), |ranges, map| map_ranges(&map.mappings, &ranges));
+        assert_eq!(folded.min(), Some(part2(&almanac).unwrap()));
+    }
+
+    #[test]
+    fn headers_name_the_categories() {
+        let almanac = parse_input(EXAMPLE).unwrap();
+        let names: Vec<&str> = almanac
+            .chain("seed", "location")
+            .unwrap()
+            .iter()
+            .map(|map| map.dest.as_str())
+            .collect();
+        assert_eq!(names, ["soil", "fertilizer", "water", "light", "temperature", "humidity", "location"]);
+    }
+
+    #[test]
+    fn any_category_translation() {
+        let almanac = parse_input(EXAMPLE).unwrap();
+        // Seed 79: soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78.
+        assert_eq!(almanac.translation("seed", "humidity").unwrap().apply(79), 78);
+        assert_eq!(almanac.translation("water", "temperature").unwrap().apply(81), 78);
+        assert_eq!(almanac.translation("soil", "soil").unwrap().apply(81), 81);
+        let err = almanac.translation("location", "seed").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (34, "a map from 'location'"));
+    }
+
+    #[test]
+    fn chain_must_reach_location() {
+        let input = EXAMPLE.replace("water-to-light", "water-to-lamp");
+        let err = parse_input(&input).unwrap_err();
+        assert_eq!(err.expected, "a map from 'lamp'");
+    }
+
+    #[test]
+    fn only_part2_needs_seeds_to_pair_up() {
+        let input = EXAMPLE.replace("seeds: 79 14 55 13", "seeds: 79 14 55");
+        // Seeds 79, 14 and 55 reach locations 82, 43 and 86.
+        assert_eq!(solve_part1(&input).unwrap(), 43);
+        let err = solve_part2(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 16));
+        assert_eq!(err.expected, "a length after the last seed");
+    }
+
+    #[test]
+    fn seeds_are_required() {
+        let err = parse_input(&EXAMPLE.replace("seeds: 79 14 55 13", "seeds:")).unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (1, 7, "a seed number"));
+    }
+
+    #[test]
+    fn duplicate_source_category_is_rejected() {
+        let input = format!("{}\nseed-to-rock map:\n1 2 3\n", EXAMPLE);
+        let err = parse_input(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (35, 1));
+    }
+
+    #[test]
+    fn overlapping_source_ranges_are_rejected() {
+        let input = "seeds: 1 2\n\nseed-to-location map:\n0 10 5\n50 14 3\n";
+        let err = parse_input(input).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 4));
+        assert_eq!(err.expected, "a source range not overlapping the mapping on line 4");
+    }
+
+    #[test]
+    fn mapping_covers_its_start_but_not_its_end() {
+        let almanac = parse_input(BOUNDARIES).unwrap();
+        assert_eq!(translate_id(9, &almanac), Ok(9));
+        assert_eq!(translate_id(10, &almanac), Ok(0));
+        assert_eq!(translate_id(19, &almanac), Ok(9));
+        assert_eq!(translate_id(20, &almanac), Ok(20));
+    }
+
+    #[test]
+    fn ranges_touching_a_mapping_are_not_mapped() {
+        // [5, 10) ends where the mapping starts, [20, 25) starts where it ends.
+        assert_eq!(solve_part2(BOUNDARIES).unwrap(), 5);
+    }
+
+    #[test]
+    fn range_matching_a_mapping_exactly_is_mapped_whole() {
+        let input = BOUNDARIES.replace("seeds: 5 5 20 5", "seeds: 10 10");
+        assert_eq!(solve_part2(&input).unwrap(), 0);
+    }
+
+    #[test]
+    fn range_around_a_mapping_keeps_both_ends() {
+        let almanac = parse_input(&BOUNDARIES.replace("seeds: 5 5 20 5", "seeds: 5 20")).unwrap();
+        let ranges = map_ranges(&almanac.maps["seed"].mappings, almanac.seed_ranges().unwrap());
+        // [10, 20) lands on [0, 10), which swallows the untouched [5, 10).
+        assert_eq!(ranges.intervals(), [Interval::new(0, 10), Interval::new(20, 25)]);
+    }
+
+    #[test]
+    fn gap_between_two_mappings_is_kept() {
+        let input = "seeds: 10 15\n\nseed-to-location map:\n100 10 5\n200 20 5\n";
+        let almanac = parse_input(input).unwrap();
+        let ranges = map_ranges(&almanac.maps["seed"].mappings, almanac.seed_ranges().unwrap());
+        assert_eq!(
+            ranges.intervals(),
+            [Interval::new(15, 20), Interval::new(100, 105), Interval::new(200, 205)]
+        );
+        assert_eq!(solve_part2(input).unwrap(), 15);
+    }
 }
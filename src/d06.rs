@@ -1,28 +1,327 @@
+use std::fmt::Display;
 
+use anyhow::Result;
 
+use crate::{
+    bignum::BigUint,
+    error::ParseError,
+    solution::Day,
+};
+
+const DAY: u8 = 6;
+
+pub struct Day06;
+
+impl Day for Day06 {
+    const DAY: u8 = DAY;
+    type Input = Sheet;
+
+    fn parse(input: &str) -> Result<Sheet> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(sheet: &Sheet) -> Result<impl Display> {
+        Ok(part1(&sheet.races))
+    }
+
+    fn part2(sheet: &Sheet) -> Result<impl Display> {
+        Ok(big_part2(&sheet.race))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
 pub struct Race {
-    pub time: i64,
-    pub record: i64,
+    pub time: u64,
+    pub record: u64,
+}
+
+/// A race whose numbers may not fit in 64 bits.
+#[derive(Clone, Debug)]
+pub struct BigRace {
+    pub time: BigUint,
+    pub record: BigUint,
+}
+
+/// The sheet read both ways: one race per column, and a single race with the columns joined.
+#[derive(Clone, Debug)]
+pub struct Sheet {
+    pub races: Vec<Race>,
+    pub race: BigRace,
+}
+
+impl BigRace {
+    /// The same race on primitive integers, when both numbers fit.
+    pub fn to_race(&self) -> Option<Race> {
+        Some(Race {
+            time: self.time.to_u64()?,
+            record: self.record.to_u64()?,
+        })
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, ParseError> {
+    Ok(part1(&parse_races(input)?))
+}
+
+/// Falls back to arbitrary precision when the joined numbers do not fit in 64 bits.
+pub fn solve_part2(input: &str) -> Result<BigUint, ParseError> {
+    Ok(big_part2(&parse_big_race(input)?))
+}
+
+/// Ways to win the joined race, on primitive integers when its numbers fit.
+pub fn big_part2(race: &BigRace) -> BigUint {
+    match race.to_race() {
+        Some(race) => BigUint::from(part2(&race)),
+        None => ways_to_win_big(race),
+    }
 }
 
-pub fn calculate_race(race: &Race) -> (f64, f64) {
-    let min = minimum(race.time, race.record);
-    let max = maximum(race.time, race.record);
-    (min, max)
+/// Product of the number of ways to beat the record of each race.
+pub fn part1(races: &[Race]) -> u64 {
+    races.iter().map(ways_to_win).product()
 }
 
-fn minimum(time: i64, min: i64) -> f64 {
-    let rad = radical(time, min);
-    f64::floor((time as f64 - rad) / 2.0)
+/// Number of ways to beat the record of the one long race.
+pub fn part2(race: &Race) -> u64 {
+    ways_to_win(race)
 }
 
-fn maximum(time: i64, min: i64) -> f64 {
-    let rad = radical(time, min);
-    f64::ceil((time as f64 + rad) / 2.0)
+/// Number of whole milliseconds the button can be held to travel strictly further than the record.
+///
+/// Holding for `h` travels `h * (time - h)`, so the winners lie strictly between the roots of
+/// `h^2 - time * h + record`. The lower root is estimated with an integer square root and then
+/// corrected against the exact distance, so ties with the record are never counted.
+pub fn ways_to_win(race: &Race) -> u64 {
+    let (time, record) = (race.time as u128, race.record as u128);
+    let distance = |hold: u128| hold * (time - hold);
+
+    let Some(discriminant) = (time * time).checked_sub(4 * record) else {
+        return 0;
+    };
+    // Largest losing hold time before the winners start.
+    let mut hold = (time - isqrt(discriminant)) / 2;
+    while distance(hold) > record {
+        hold -= 1;
+    }
+    while hold < time / 2 && distance(hold + 1) <= record {
+        hold += 1;
+    }
+    // The winners are symmetric around time / 2.
+    (time.saturating_sub(2 * hold + 1)) as u64
 }
 
-fn radical(time: i64, min: i64) -> f64 {
-    let inner = time.pow(2) - 4 * min;
-    f64::sqrt(inner as f64)
+/// [`ways_to_win`] for races beyond 64 bits.
+pub fn ways_to_win_big(race: &BigRace) -> BigUint {
+    let (time, record) = (&race.time, &race.record);
+    let one = BigUint::from(1);
+    let two = BigUint::from(2);
+    let four = BigUint::from(4);
+    let distance = |hold: &BigUint| hold * &(time - hold);
+
+    let square = time * time;
+    let four_record = &four * record;
+    if square < four_record {
+        return BigUint::zero();
+    }
+    let root = (&square - &four_record).isqrt();
+    let half_time = half(time);
+    let mut hold = half(&(time - &root));
+    while distance(&hold) > *record {
+        hold = &hold - &one;
+    }
+    while hold < half_time && distance(&(&hold + &one)) <= *record {
+        hold = &hold + &one;
+    }
+    let losers = &(&two * &hold) + &one;
+    if *time > losers {
+        time - &losers
+    } else {
+        BigUint::zero()
+    }
 }
 
+fn half(value: &BigUint) -> BigUint {
+    value.div_rem(2).0
+}
+
+/// Largest integer whose square is at most `n`: a floating point estimate, corrected.
+pub fn isqrt(n: u128) -> u128 {
+    let mut root = (n as f64).sqrt() as u128;
+    while root.checked_mul(root).is_none_or(|square| square > n) {
+        root -= 1;
+    }
+    while (root + 1).checked_mul(root + 1).is_some_and(|square| square <= n) {
+        root += 1;
+    }
+    root
+}
+
+pub fn parse_input(input: &str) -> Result<Sheet, ParseError> {
+    Ok(Sheet {
+        races: parse_races(input)?,
+        race: parse_big_race(input)?,
+    })
+}
+
+pub fn parse_races(input: &str) -> Result<Vec<Race>, ParseError> {
+    let (times, records) = parse_sheet(input)?;
+
+    let times = parse_numbers(&times)?;
+    let records = parse_numbers(&records)?;
+
+    Ok(times.into_iter().zip(records).map(|(time, record)| Race { time, record }).collect())
+}
+
+/// Reads the sheet as a single race, ignoring the spaces between the numbers of each line.
+pub fn parse_big_race(input: &str) -> Result<BigRace, ParseError> {
+    let (times, records) = parse_sheet(input)?;
+
+    Ok(BigRace {
+        time: parse_joined_big(&times)?,
+        record: parse_joined_big(&records)?,
+    })
+}
+
+/// Numbers of a sheet line, with enough context to point at any of them.
+struct SheetLine<'a> {
+    line_no: usize,
+    line: &'a str,
+    tokens: Vec<&'a str>,
+}
+
+fn parse_sheet(input: &str) -> Result<(SheetLine<'_>, SheetLine<'_>), ParseError> {
+    let mut lines = input.lines().enumerate();
+    let times = sheet_line(input, lines.next(), "Time:")?;
+    let records = sheet_line(input, lines.next(), "Distance:")?;
+
+    if times.tokens.is_empty() {
+        return Err(ParseError::at(DAY, times.line_no, times.line, "", "at least one race time"));
+    }
+    if times.tokens.len() != records.tokens.len() {
+        return Err(ParseError::at(
+            DAY,
+            records.line_no,
+            records.line,
+            "",
+            format!("{} distances, one per time", times.tokens.len()),
+        ));
+    }
+
+    Ok((times, records))
+}
+
+fn sheet_line<'a>(input: &str, line: Option<(usize, &'a str)>, label: &str) -> Result<SheetLine<'a>, ParseError> {
+    let (idx, line) = line.ok_or_else(|| ParseError::end_of_input(DAY, input, format!("a '{}' line", label)))?;
+    let numbers = line
+        .strip_prefix(label)
+        .ok_or_else(|| ParseError::at(DAY, idx + 1, line, line, format!("'{}'", label)))?;
+
+    Ok(SheetLine {
+        line_no: idx + 1,
+        line,
+        tokens: numbers.split_whitespace().collect(),
+    })
+}
+
+fn parse_numbers(sheet: &SheetLine) -> Result<Vec<u64>, ParseError> {
+    sheet
+        .tokens
+        .iter()
+        .map(|token| {
+            token
+                .parse::<u64>()
+                .map_err(|_| ParseError::at_token(DAY, sheet.line_no, sheet.line, token, "a number"))
+        })
+        .collect()
+}
+
+fn parse_joined_big(sheet: &SheetLine) -> Result<BigUint, ParseError> {
+    for token in &sheet.tokens {
+        if !token.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ParseError::at_token(DAY, sheet.line_no, sheet.line, token, "a number"));
+        }
+    }
+    BigUint::parse_decimal(&sheet.tokens.concat())
+        .ok_or_else(|| ParseError::at(DAY, sheet.line_no, sheet.line, "", "a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    fn brute_force(race: &Race) -> u64 {
+        (0..=race.time).filter(|hold| hold * (race.time - hold) > race.record).count() as u64
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 288);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), BigUint::from(71503));
+    }
+
+    #[test]
+    fn tying_the_record_does_not_win() {
+        // Holding 10 or 20 ms travels exactly 200 mm.
+        assert_eq!(ways_to_win(&Race { time: 30, record: 200 }), 9);
+        // Only holding 2 ms ties, nothing beats it.
+        assert_eq!(ways_to_win(&Race { time: 4, record: 4 }), 0);
+    }
+
+    #[test]
+    fn matches_brute_force_on_small_races() {
+        for time in 0..60 {
+            for record in 0..=time * time / 4 + 1 {
+                let race = Race { time, record };
+                assert_eq!(ways_to_win(&race), brute_force(&race), "{:?}", race);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_for_times_beyond_f64_precision() {
+        // 2^53 + 1 cannot be represented as an f64; holding 3 ms ties the record.
+        let time = (1 << 53) + 1;
+        let race = Race {
+            time,
+            record: 3 * (time - 3),
+        };
+        assert_eq!(ways_to_win(&race), time - 7);
+        assert_eq!(ways_to_win(&Race { time: u64::MAX, record: 0 }), u64::MAX - 1);
+    }
+
+    #[test]
+    fn big_path_agrees_with_the_primitive_one() {
+        for (time, record) in [(7, 9), (30, 200), (4, 4), (3, 2), (71530, 940200), (u64::MAX, u64::MAX)] {
+            let race = Race { time, record };
+            let big = BigRace {
+                time: BigUint::from(time),
+                record: BigUint::from(record),
+            };
+            assert_eq!(ways_to_win_big(&big), BigUint::from(ways_to_win(&race)), "{:?}", race);
+        }
+    }
+
+    #[test]
+    fn part2_beyond_64_bits() {
+        // Holding 10^19 of 10^20 ties: 10^19 * 9 * 10^19 = 9 * 10^38.
+        let sheet = "Time: 1000000 0000000 0000000\nDistance: 90000000000000 0000000000000 000000000000\n";
+        assert!(parse_big_race(sheet).unwrap().to_race().is_none());
+        let expected = BigUint::parse_decimal("79999999999999999999").unwrap();
+        assert_eq!(solve_part2(sheet).unwrap(), expected);
+    }
+
+    #[test]
+    fn sheet_needs_a_race() {
+        let err = solve_part1("Time:\nDistance:\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (1, 6, "at least one race time"));
+    }
+}
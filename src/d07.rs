@@ -0,0 +1,210 @@
+use std::fmt::{Display, Write};
+
+use anyhow::Result;
+
+use crate::{
+    camel_cards::{Card, Hand, HandError, RuleSet, Rules},
+    error::ParseError,
+    solution::Day,
+};
+
+const DAY: u8 = 7;
+
+pub struct Day07;
+
+impl Day for Day07 {
+    const DAY: u8 = DAY;
+    type Input = Vec<Deal>;
+
+    fn parse(input: &str) -> Result<Vec<Deal>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<Deal>) -> Result<impl Display> {
+        Ok(part1(input)?)
+    }
+
+    fn part2(input: &Vec<Deal>) -> Result<impl Display> {
+        Ok(part2(input)?)
+    }
+}
+
+/// One line of the input: the cards as dealt and the bid on them.
+#[derive(Clone, Debug)]
+pub struct Deal {
+    pub cards: Vec<Card>,
+    pub bid: u64,
+}
+
+pub fn solve_part1(input: &str) -> Result<u64> {
+    Ok(part1(&parse_input(input)?)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64> {
+    Ok(part2(&parse_input(input)?)?)
+}
+
+/// Total winnings: each bid multiplied by the rank of its hand.
+pub fn part1(deals: &[Deal]) -> Result<u64, HandError> {
+    total_winnings(deals, &RuleSet::standard())
+}
+
+/// Total winnings when 'J' is a joker: the weakest card, but wild when picking the hand type.
+pub fn part2(deals: &[Deal]) -> Result<u64, HandError> {
+    total_winnings(deals, &RuleSet::jokers())
+}
+
+/// Each hand evaluated under `rules` with its bid, from the weakest (rank 1) to the strongest.
+pub fn ranked(deals: &[Deal], rules: &impl Rules) -> Result<Vec<(Hand, u64)>, HandError> {
+    let mut hands = deals
+        .iter()
+        .map(|deal| Ok((rules.evaluate(&deal.cards)?, deal.bid)))
+        .collect::<Result<Vec<_>, _>>()?;
+    hands.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(hands)
+}
+
+pub fn total_winnings(deals: &[Deal], rules: &impl Rules) -> Result<u64, HandError> {
+    Ok(ranked(deals, rules)?
+        .iter()
+        .enumerate()
+        .map(|(i, (_, bid))| (i as u64 + 1) * bid)
+        .sum())
+}
+
+/// One line per hand, weakest first, showing how it was ranked: its type, the card its
+/// wildcards stand for, its rank and what it wins. Meant to be diffed against expectations.
+pub fn explain(deals: &[Deal], rules: &impl Rules) -> Result<String, HandError> {
+    let mut out = format!(
+        "{:>5}  {:<8} {:<16} {:<10} {:>6} {:>10}\n",
+        "Rank", "Cards", "Type", "Wildcards", "Bid", "Winnings"
+    );
+    let mut total = 0;
+    for (i, (hand, bid)) in ranked(deals, rules)?.iter().enumerate() {
+        let rank = i as u64 + 1;
+        let wild: String = hand.cards().iter().filter(|card| rules.is_wild(**card)).collect();
+        let wildcards = match rules.wild_target(hand.cards()) {
+            Some(target) => format!("{} as {}", wild, target),
+            None => "-".to_string(),
+        };
+        total += rank * bid;
+        let _ = writeln!(
+            out,
+            "{:>5}  {:<8} {:<16} {:<10} {:>6} {:>10}",
+            rank,
+            hand.to_string(),
+            hand.hand_type().to_string(),
+            wildcards,
+            bid,
+            rank * bid
+        );
+    }
+    let _ = writeln!(out, "Total winnings: {}", total);
+    Ok(out)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Deal>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_deal(i + 1, line))
+        .collect()
+}
+
+fn parse_deal(line_no: usize, line: &str) -> Result<Deal, ParseError> {
+    let (hand_str, bid_str) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::at(DAY, line_no, line, "", "a space between the cards and the bid"))?;
+
+    let rules = RuleSet::standard();
+    let cards: Vec<Card> = hand_str.chars().collect();
+    match rules.evaluate(&cards) {
+        Ok(_) => {}
+        Err(HandError::UnknownCard { position, .. }) => {
+            let (col, _) = hand_str.char_indices().nth(position).unwrap();
+            let labels: String = rules.deck().iter().collect();
+            return Err(ParseError::at(DAY, line_no, line, &line[col..], format!("a card label ({})", labels)));
+        }
+        Err(HandError::WrongSize { expected, .. }) => {
+            let rest = &line[hand_str.len()..];
+            return Err(ParseError::at(DAY, line_no, line, rest, format!("{} cards per hand", expected)));
+        }
+    }
+
+    let bid = bid_str
+        .parse()
+        .map_err(|_| ParseError::at(DAY, line_no, line, bid_str, "a bid amount"))?;
+
+    Ok(Deal { cards, bid })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 6440);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), 5905);
+    }
+
+    #[test]
+    fn all_jokers_is_the_weakest_five_of_a_kind() {
+        let hands = "JJJJJ 1\n2222J 3";
+        // Without jokers 2222J is only four of a kind and ranks first.
+        assert_eq!(solve_part1(hands).unwrap(), 5);
+        assert_eq!(solve_part2(hands).unwrap(), 7);
+    }
+
+    #[test]
+    fn ranks_follow_the_rules() {
+        let deals = parse_input(EXAMPLE).unwrap();
+        let order = |rules: &RuleSet| -> Vec<u64> { ranked(&deals, rules).unwrap().iter().map(|(_, bid)| *bid).collect() };
+        assert_eq!(order(&RuleSet::standard()), [765, 220, 28, 684, 483]);
+        assert_eq!(order(&RuleSet::jokers()), [765, 28, 684, 483, 220]);
+    }
+
+    #[test]
+    fn explain_lists_hands_by_rank() {
+        let deals = parse_input(EXAMPLE).unwrap();
+        let explanation = explain(&deals, &RuleSet::jokers()).unwrap();
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[1].split_whitespace().collect::<Vec<_>>(), ["1", "32T3K", "one", "pair", "-", "765", "765"]);
+        assert_eq!(
+            lines[5].split_whitespace().collect::<Vec<_>>(),
+            ["5", "KTJJT", "four", "of", "a", "kind", "JJ", "as", "T", "220", "1100"]
+        );
+        assert_eq!(lines[6], "Total winnings: 5905");
+    }
+
+    #[test]
+    fn rejects_unknown_labels_and_short_hands() {
+        let err = parse_input("32T3K 765\n32X3K 1").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = parse_input("32T3 765").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+    }
+
+    #[test]
+    fn unparsed_deals_are_checked() {
+        let deals = [Deal {
+            cards: "32X3K".chars().collect(),
+            bid: 1,
+        }];
+        assert_eq!(part1(&deals), Err(HandError::UnknownCard { position: 2, card: 'X' }));
+        assert!(explain(&deals, &RuleSet::jokers()).is_err());
+    }
+}
@@ -0,0 +1,656 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+};
+
+use anyhow::{anyhow, bail, Result};
+use nom::bytes::complete::tag;
+use nom::character::complete::alphanumeric1;
+use nom::combinator::eof;
+use nom::error::{context, VerboseError};
+use nom::sequence::{delimited, separated_pair, terminated};
+
+use crate::{
+    animation::Animate,
+    cycles::{self, Cycle},
+    error::ParseError,
+    number_theory::Congruence,
+    render::{paint, Colour, Mode},
+    solution::Day,
+};
+
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+const DAY: u8 = 8;
+
+pub struct Day08;
+
+impl Day for Day08 {
+    const DAY: u8 = DAY;
+    type Input = PuzzleInput;
+
+    fn parse(input: &str) -> Result<PuzzleInput> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &PuzzleInput) -> Result<impl Display> {
+        part1(input)
+    }
+
+    fn part2(input: &PuzzleInput) -> Result<impl Display> {
+        part2(input)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+/// Index of a node in its [`Network`].
+pub type NodeId = u32;
+
+/// The desert nodes, each named once: ids index the name table and the edge table, so walking
+/// the network never hashes a name.
+#[derive(Debug, Default)]
+pub struct Network {
+    names: Vec<String>,
+    /// Left and right neighbour of each node.
+    edges: Vec<(NodeId, NodeId)>,
+    ids: HashMap<String, NodeId>,
+}
+
+impl Network {
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id as usize]
+    }
+
+    /// Every node, in the order the map defines them.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.names.len() as NodeId
+    }
+
+    /// Nodes whose name ends with `suffix`.
+    pub fn nodes_ending_with<'a>(&'a self, suffix: &'a str) -> impl Iterator<Item = NodeId> + 'a {
+        self.nodes().filter(move |id| self.name(*id).ends_with(suffix))
+    }
+
+    pub fn edges(&self, id: NodeId) -> (NodeId, NodeId) {
+        self.edges[id as usize]
+    }
+
+    pub fn step(&self, id: NodeId, instr: Instruction) -> NodeId {
+        let (left, right) = self.edges(id);
+        match instr {
+            Instruction::Left => left,
+            Instruction::Right => right,
+        }
+    }
+
+    /// Graphviz description of the network, one edge per direction labelled L or R (LR when
+    /// both lead to the same node).
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(|_| vec![], |_, _| vec![])
+    }
+
+    /// Like [`Network::to_dot`], with extra Graphviz attributes (e.g. `color=red`) for each
+    /// node and for each edge, given as its two ends.
+    pub fn to_dot_with(
+        &self,
+        node_attrs: impl Fn(NodeId) -> Vec<String>,
+        edge_attrs: impl Fn(NodeId, NodeId) -> Vec<String>,
+    ) -> String {
+        let mut out = "digraph desert {\n".to_string();
+        for id in self.nodes() {
+            let _ = writeln!(out, "  \"{}\"{};", self.name(id), dot_attrs(node_attrs(id)));
+        }
+        for id in self.nodes() {
+            let (left, right) = self.edges(id);
+            let edges: &[(NodeId, &str)] = if left == right {
+                &[(left, "LR")]
+            } else {
+                &[(left, "L"), (right, "R")]
+            };
+            for (target, label) in edges {
+                let mut attrs = vec![format!("label=\"{}\"", label)];
+                attrs.extend(edge_attrs(id, *target));
+                let _ = writeln!(out, "  \"{}\" -> \"{}\"{};", self.name(id), self.name(*target), dot_attrs(attrs));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn dot_attrs(attrs: Vec<String>) -> String {
+    if attrs.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", attrs.join(", "))
+    }
+}
+
+/// Colours of the ghosts' loops in [`ghost_dot`], reused when there are more ghosts.
+const LOOP_COLOURS: [&str; 6] = ["blue", "darkorange", "purple", "deeppink", "teal", "brown"];
+
+/// The network as Graphviz, with start nodes (ending in A) filled green, end nodes (ending in
+/// Z) filled red, and the nodes and edges of each ghost's loop drawn in that ghost's colour.
+/// A comment per ghost gives its lead-in, loop length and hits, which is what the CRT in
+/// [`earliest_common_hit`] works from.
+pub fn ghost_dot(puzzle: &PuzzleInput) -> String {
+    let network = &puzzle.network;
+    let mut node_colours = HashMap::<NodeId, &str>::new();
+    let mut edge_colours = HashMap::<(NodeId, NodeId), &str>::new();
+    let mut comments = String::new();
+
+    for (ghost, start) in network.nodes_ending_with("A").enumerate() {
+        let colour = LOOP_COLOURS[ghost % LOOP_COLOURS.len()];
+        let ghost_loop = find_loop(puzzle, start);
+        for (from, to) in loop_edges(puzzle, start, &ghost_loop) {
+            node_colours.entry(from).or_insert(colour);
+            edge_colours.entry((from, to)).or_insert(colour);
+        }
+        let _ = writeln!(
+            comments,
+            "// {} ({}): lead-in {} steps, loop {} steps, hits {:?} then {:?} every loop",
+            network.name(start),
+            colour,
+            ghost_loop.cycle.start,
+            ghost_loop.cycle.length,
+            ghost_loop.lead_in_hits,
+            ghost_loop.loop_hits
+        );
+    }
+
+    let node_attrs = |id: NodeId| {
+        let name = network.name(id);
+        let mut attrs = vec![];
+        if name.ends_with('A') {
+            attrs.extend(["style=filled".to_string(), "fillcolor=palegreen".to_string()]);
+        } else if name.ends_with('Z') {
+            attrs.extend(["style=filled".to_string(), "fillcolor=lightcoral".to_string()]);
+        }
+        if let Some(colour) = node_colours.get(&id) {
+            attrs.extend([format!("color={}", colour), "penwidth=2".to_string()]);
+        }
+        attrs
+    };
+    let edge_attrs = |from: NodeId, to: NodeId| match edge_colours.get(&(from, to)) {
+        Some(colour) => vec![format!("color={}", colour), "penwidth=2".to_string()],
+        None => vec![],
+    };
+
+    comments + &network.to_dot_with(node_attrs, edge_attrs)
+}
+
+/// Edges a ghost starting at `start` keeps following once it is inside its loop.
+pub fn loop_edges(puzzle: &PuzzleInput, start: NodeId, ghost_loop: &GhostLoop) -> Vec<(NodeId, NodeId)> {
+    let mut node = start;
+    let mut edges = vec![];
+    let steps = puzzle.instructions.iter().cycle();
+    for (i, instr) in steps.take(ghost_loop.cycle.start + ghost_loop.cycle.length).enumerate() {
+        let next = puzzle.apply_instr(*instr, node);
+        if i >= ghost_loop.cycle.start {
+            edges.push((node, next));
+        }
+        node = next;
+    }
+    edges
+}
+
+/// The L/R instructions and the network they are followed through.
+#[derive(Debug)]
+pub struct PuzzleInput {
+    pub instructions: Vec<Instruction>,
+    pub network: Network,
+}
+
+impl PuzzleInput {
+    pub fn apply_instr(&self, instr: Instruction, pos: NodeId) -> NodeId {
+        self.network.step(pos, instr)
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u64> {
+    part1(&parse_input(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<u64> {
+    part2(&parse_input(input)?)
+}
+
+/// Steps needed to go from AAA to ZZZ.
+pub fn part1(puzzle: &PuzzleInput) -> Result<u64> {
+    let mut walk = GhostWalk::part1(puzzle)?;
+    // Where the walk goes next depends only on its node and its place in the instructions, so
+    // once it has been in more states than there are, it is going round a loop without ZZZ.
+    let states = (puzzle.network.len() * puzzle.instructions.len()) as u64;
+    while !walk.is_done() {
+        if walk.steps() >= states {
+            bail!("ZZZ cannot be reached from AAA");
+        }
+        walk.advance();
+    }
+    Ok(walk.steps())
+}
+
+/// Steps needed until every ghost, starting on the nodes ending in A, stands on a node ending in Z.
+pub fn part2(puzzle: &PuzzleInput) -> Result<u64> {
+    let loops: Vec<GhostLoop> = ghost_starts(puzzle)?.into_iter().map(|start| find_loop(puzzle, start)).collect();
+    earliest_common_hit(&loops).ok_or_else(|| anyhow!("The ghosts never stand on nodes ending in Z at the same time"))
+}
+
+fn ghost_starts(puzzle: &PuzzleInput) -> Result<Vec<NodeId>> {
+    let starts: Vec<NodeId> = puzzle.network.nodes_ending_with("A").collect();
+    if starts.is_empty() {
+        bail!("The map has no nodes ending in A to start from");
+    }
+    Ok(starts)
+}
+
+/// Ghosts following the instructions together, one step at a time, until all of them stand on
+/// an end node. Part 1 is the walk of a single ghost; part 2 is too long to walk, but watching
+/// its start with `--animate` shows the lead-ins and loops [`find_loop`] works out.
+pub struct GhostWalk<'a> {
+    puzzle: &'a PuzzleInput,
+    starts: Vec<NodeId>,
+    nodes: Vec<NodeId>,
+    /// Whether each node of the network is an end node.
+    ends: Vec<bool>,
+    steps: u64,
+}
+
+impl<'a> GhostWalk<'a> {
+    pub fn new(puzzle: &'a PuzzleInput, starts: Vec<NodeId>, is_end: impl Fn(NodeId) -> bool) -> Self {
+        Self {
+            puzzle,
+            nodes: starts.clone(),
+            starts,
+            ends: puzzle.network.nodes().map(is_end).collect(),
+            steps: 0,
+        }
+    }
+
+    /// The walk of part 1, from AAA to ZZZ.
+    pub fn part1(puzzle: &'a PuzzleInput) -> Result<Self> {
+        let Some(start) = puzzle.network.id("AAA") else {
+            bail!("The map has no node AAA to start from");
+        };
+        let Some(end) = puzzle.network.id("ZZZ") else {
+            bail!("The map has no node ZZZ to reach");
+        };
+        Ok(Self::new(puzzle, vec![start], |id| id == end))
+    }
+
+    /// The walk of part 2, from every node ending in A to nodes ending in Z.
+    pub fn part2(puzzle: &'a PuzzleInput) -> Result<Self> {
+        let network = &puzzle.network;
+        Ok(Self::new(puzzle, ghost_starts(puzzle)?, |id| network.name(id).ends_with('Z')))
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Where each ghost stands, in the order of their starts.
+    pub fn nodes(&self) -> &[NodeId] {
+        &self.nodes
+    }
+
+    /// Index of the instruction the next step follows.
+    fn next_instruction(&self) -> usize {
+        (self.steps % self.puzzle.instructions.len() as u64) as usize
+    }
+}
+
+/// The step count and next instruction, then one line per ghost with its start and where it
+/// stands now, in green on an end node.
+impl Animate for GhostWalk<'_> {
+    fn frame(&self, mode: Mode) -> String {
+        let network = &self.puzzle.network;
+        let index = self.next_instruction();
+        let mut out = format!(
+            "Step {}, next instruction {} of {}: {:?}\n",
+            self.steps,
+            index + 1,
+            self.puzzle.instructions.len(),
+            self.puzzle.instructions[index]
+        );
+        for (start, node) in self.starts.iter().zip(&self.nodes) {
+            let colour = self.ends[*node as usize].then_some(Colour::Green);
+            let _ = writeln!(out, "{} -> {}", network.name(*start), paint(network.name(*node), colour, mode));
+        }
+        out
+    }
+
+    fn is_done(&self) -> bool {
+        self.nodes.iter().all(|node| self.ends[*node as usize])
+    }
+
+    fn advance(&mut self) {
+        let instr = self.puzzle.instructions[self.next_instruction()];
+        for node in &mut self.nodes {
+            *node = self.puzzle.apply_instr(instr, *node);
+        }
+        self.steps += 1;
+    }
+}
+
+/// When one ghost stands on a node ending in Z. Its walk is a lead-in followed by a loop that
+/// repeats forever, so the hits inside the loop come back every `cycle.length` steps.
+#[derive(Debug)]
+pub struct GhostLoop {
+    pub cycle: Cycle,
+    /// Steps, before the loop starts, that end on a Z node. They never happen again.
+    pub lead_in_hits: Vec<u64>,
+    /// Steps during the first pass through the loop that end on a Z node.
+    pub loop_hits: Vec<u64>,
+}
+
+impl GhostLoop {
+    pub fn is_hit(&self, step: u64) -> bool {
+        if step < self.cycle.start as u64 {
+            self.lead_in_hits.contains(&step)
+        } else {
+            let step = self.cycle.first_occurrence(step as usize) as u64;
+            self.loop_hits.contains(&step)
+        }
+    }
+}
+
+/// Walks from `start` until the ghost is back on a node at the same point of the instructions,
+/// noting every step that ends on a node ending in Z.
+pub fn find_loop(puzzle: &PuzzleInput, start: NodeId) -> GhostLoop {
+    let step = |&(node, instr_id): &(NodeId, usize)| {
+        let next = puzzle.apply_instr(puzzle.instructions[instr_id], node);
+        (next, (instr_id + 1) % puzzle.instructions.len())
+    };
+    let cycle = cycles::brent((start, 0), step);
+
+    let mut state = (start, 0);
+    let mut lead_in_hits = vec![];
+    let mut loop_hits = vec![];
+    for i in 0..cycle.start + cycle.length {
+        if puzzle.network.name(state.0).ends_with('Z') {
+            if i < cycle.start {
+                lead_in_hits.push(i as u64);
+            } else {
+                loop_hits.push(i as u64);
+            }
+        }
+        state = step(&state);
+    }
+
+    GhostLoop {
+        cycle,
+        lead_in_hits,
+        loop_hits,
+    }
+}
+
+/// First step at which every ghost is on a hit, if there is one.
+pub fn earliest_common_hit(loops: &[GhostLoop]) -> Option<u64> {
+    // Until the longest lead-in is over, that ghost can only be on one of its lead-in hits.
+    let all_looping = loops.iter().map(|l| l.cycle.start as u64).max()?;
+    let early = loops
+        .iter()
+        .flat_map(|l| &l.lead_in_hits)
+        .filter(|step| **step < all_looping && loops.iter().all(|l| l.is_hit(**step)))
+        .min();
+    if let Some(step) = early {
+        return Some(*step);
+    }
+
+    // From then on each ghost is on a hit when the step matches one of its loop hits modulo the
+    // loop length, whatever the lengths have in common.
+    let mut solutions = vec![Congruence::new(0, 1)];
+    for ghost in loops {
+        let modulus = ghost.cycle.length as u64;
+        solutions = solutions
+            .iter()
+            .flat_map(|solution| {
+                ghost
+                    .loop_hits
+                    .iter()
+                    .filter_map(move |hit| solution.combine(&Congruence::new(*hit, modulus)))
+            })
+            .collect();
+    }
+    solutions.iter().filter_map(|solution| solution.first_at_least(all_looping)).min()
+}
+
+pub fn parse_input(input: &str) -> Result<PuzzleInput, ParseError> {
+    let mut lines = input.lines().enumerate();
+
+    let (_, instr_line) = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(DAY, input, "a line of L/R instructions"))?;
+    let instructions = instr_line
+        .char_indices()
+        .map(|(col, c)| match c {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(ParseError::at(DAY, 1, instr_line, &instr_line[col..], "an instruction (L or R)")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if instructions.is_empty() {
+        return Err(ParseError::new(DAY, 1, 1, "at least one instruction"));
+    }
+
+    if let Some((idx, line)) = lines.next() {
+        if !line.is_empty() {
+            return Err(ParseError::at(DAY, idx + 1, line, line, "an empty line after the instructions"));
+        }
+    }
+
+    // Name every node first, so that edges can point at nodes defined further down.
+    let mut network = Network::default();
+    let mut node_lines = vec![];
+    for (idx, line) in lines {
+        let (_, (origin, targets)) = terminated(node_line, context("end of line", eof))(line)
+            .map_err(|err| ParseError::from_nom(DAY, idx + 1, line, err))?;
+        if let Some(first) = network.ids.get(origin) {
+            let (first_line, _, _) = node_lines[*first as usize];
+            return Err(ParseError::at_token(
+                DAY,
+                idx + 1,
+                line,
+                origin,
+                format!("a node not already defined on line {}", first_line),
+            ));
+        }
+        network.ids.insert(origin.to_string(), network.names.len() as NodeId);
+        network.names.push(origin.to_string());
+        node_lines.push((idx + 1, line, targets));
+    }
+
+    for (line_no, line, (left, right)) in node_lines {
+        let resolve = |target: &str| {
+            network
+                .id(target)
+                .ok_or_else(|| ParseError::at_token(DAY, line_no, line, target, "a node defined in the map"))
+        };
+        let edges = (resolve(left)?, resolve(right)?);
+        network.edges.push(edges);
+    }
+
+    Ok(PuzzleInput { instructions, network })
+}
+
+fn node_line(input: &str) -> IResult<'_, (&str, (&str, &str))> {
+    let node_pair = delimited(
+        context("'('", tag("(")),
+        separated_pair(node_id, context("', '", tag(", ")), node_id),
+        context("')'", tag(")")),
+    );
+    separated_pair(node_id, context("' = '", tag(" = ")), node_pair)(input)
+}
+
+fn node_id(input: &str) -> IResult<'_, &str> {
+    context("a node id", alphanumeric1)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_1: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE_2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE_GHOSTS: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(solve_part1(EXAMPLE_1).unwrap(), 2);
+        assert_eq!(solve_part1(EXAMPLE_2).unwrap(), 6);
+    }
+
+    #[test]
+    fn unreachable_zzz_is_an_error() {
+        let map = "LR\n\nAAA = (BBB, AAA)\nBBB = (AAA, BBB)\nZZZ = (AAA, AAA)";
+        assert_eq!(solve_part1(map).unwrap_err().to_string(), "ZZZ cannot be reached from AAA");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE_GHOSTS).unwrap(), 6);
+    }
+
+    #[test]
+    fn walking_the_ghosts_agrees_with_the_loops() {
+        let puzzle = parse_input(EXAMPLE_GHOSTS).unwrap();
+        let mut walk = GhostWalk::part2(&puzzle).unwrap();
+        assert_eq!(walk.frame(Mode::Plain), "Step 0, next instruction 1 of 2: Left\n11A -> 11A\n22A -> 22A\n");
+        walk.advance();
+        walk.advance();
+        assert_eq!(walk.frame(Mode::Plain), "Step 2, next instruction 1 of 2: Left\n11A -> 11Z\n22A -> 22C\n");
+        while !walk.is_done() {
+            walk.advance();
+        }
+        assert_eq!(walk.steps(), part2(&puzzle).unwrap());
+    }
+
+    #[test]
+    fn ghosts_with_hits_before_their_loop() {
+        // The first ghost passes 1Z once on its way into the 1B-1C loop.
+        let map = "L\n\n1A = (1Z, 1Z)\n1Z = (1B, 1B)\n1B = (1C, 1C)\n1C = (1B, 1B)\n\
+                   2A = (2Z, 2Z)\n2Z = (2X, 2X)\n2X = (2Z, 2Z)";
+        let puzzle = parse_input(map).unwrap();
+        let ghost = find_loop(&puzzle, puzzle.network.id("1A").unwrap());
+        assert_eq!(ghost.cycle, Cycle { start: 2, length: 2 });
+        assert_eq!((ghost.lead_in_hits.as_slice(), ghost.loop_hits.as_slice()), ([1].as_slice(), [].as_slice()));
+        assert_eq!(part2(&puzzle).unwrap(), 1);
+    }
+
+    #[test]
+    fn ghosts_with_several_hits_per_loop_and_shared_factors() {
+        // Loops of 4 and 6 steps: hits at 2, 3 (mod 4) and 5 (mod 6) first meet at 11, not 12.
+        let map = "L\n\n1A = (1B, 1B)\n1B = (1PZ, 1PZ)\n1PZ = (1QZ, 1QZ)\n1QZ = (1C, 1C)\n1C = (1B, 1B)\n\
+                   2A = (2B, 2B)\n2B = (2C, 2C)\n2C = (2D, 2D)\n2D = (2E, 2E)\n2E = (2Z, 2Z)\n2Z = (2A, 2A)";
+        let puzzle = parse_input(map).unwrap();
+        let ghost = find_loop(&puzzle, puzzle.network.id("1A").unwrap());
+        assert_eq!(ghost.cycle, Cycle { start: 1, length: 4 });
+        assert_eq!(ghost.loop_hits, [2, 3]);
+        assert_eq!(part2(&puzzle).unwrap(), 11);
+    }
+
+    #[test]
+    fn ghosts_that_never_meet() {
+        // Hits at odd steps for one ghost and even steps for the other.
+        let map = "L\n\n1A = (1Z, 1Z)\n1Z = (1A, 1A)\n2A = (2B, 2B)\n2B = (2Z, 2Z)\n2Z = (2B, 2B)";
+        assert!(solve_part2(map).is_err());
+    }
+
+    #[test]
+    fn network_is_interned_in_map_order() {
+        let puzzle = parse_input(EXAMPLE_2).unwrap();
+        let network = &puzzle.network;
+        assert_eq!(network.len(), 3);
+        assert_eq!(network.nodes().map(|id| network.name(id)).collect::<Vec<_>>(), ["AAA", "BBB", "ZZZ"]);
+        let bbb = network.id("BBB").unwrap();
+        assert_eq!(network.edges(bbb), (0, 2));
+        assert_eq!(network.step(bbb, Instruction::Right), network.id("ZZZ").unwrap());
+        assert_eq!(puzzle.instructions, [Instruction::Left, Instruction::Left, Instruction::Right]);
+    }
+
+    #[test]
+    fn dot_export_lists_every_edge() {
+        let dot = parse_input(EXAMPLE_2).unwrap().network.to_dot();
+        assert!(dot.starts_with("digraph desert {\n"));
+        assert!(dot.contains("  \"AAA\" -> \"BBB\" [label=\"LR\"];\n"));
+        assert!(dot.contains("  \"BBB\" -> \"AAA\" [label=\"L\"];\n"));
+        assert!(dot.contains("  \"BBB\" -> \"ZZZ\" [label=\"R\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn ghost_dot_colours_starts_ends_and_loops() {
+        let puzzle = parse_input(EXAMPLE_GHOSTS).unwrap();
+        let start = puzzle.network.id("22A").unwrap();
+        let edges = loop_edges(&puzzle, start, &find_loop(&puzzle, start));
+        let names: Vec<_> = edges.iter().map(|(a, b)| (puzzle.network.name(*a), puzzle.network.name(*b))).collect();
+        assert_eq!(names, [("22B", "22C"), ("22C", "22Z"), ("22Z", "22B"), ("22B", "22C"), ("22C", "22Z"), ("22Z", "22B")]);
+
+        let dot = ghost_dot(&puzzle);
+        assert!(dot.starts_with("// 11A (blue): lead-in 1 steps, loop 2 steps, hits [] then [2] every loop\n"));
+        assert!(dot.contains("  \"11A\" [style=filled, fillcolor=palegreen];\n"));
+        assert!(dot.contains("  \"22Z\" [style=filled, fillcolor=lightcoral, color=darkorange, penwidth=2];\n"));
+        assert!(dot.contains("  \"22C\" -> \"22Z\" [label=\"LR\", color=darkorange, penwidth=2];\n"));
+        assert!(dot.contains("  \"XXX\";\n"));
+    }
+
+    #[test]
+    fn duplicate_node_is_rejected() {
+        let err = parse_input("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.expected, "a node not already defined on line 3");
+    }
+
+    #[test]
+    fn undefined_node_is_rejected() {
+        let err = solve_part1("L\n\nAAA = (ZZZ, BBB)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (3, 13));
+    }
+}
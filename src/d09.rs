@@ -0,0 +1,118 @@
+use std::fmt::Display;
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    error::ParseError,
+    oasis::DifferenceTable,
+    solution::Day,
+};
+
+const DAY: u8 = 9;
+
+pub struct Day09;
+
+impl Day for Day09 {
+    const DAY: u8 = DAY;
+    type Input = Vec<History>;
+
+    fn parse(input: &str) -> Result<Vec<History>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<History>) -> Result<impl Display> {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<History>) -> Result<impl Display> {
+        part2(input)
+    }
+}
+
+/// One line of the OASIS report: the values of a reading over time.
+pub type History = Vec<i64>;
+
+pub fn solve_part1(input: &str) -> Result<i64> {
+    part1(&parse_input(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<i64> {
+    part2(&parse_input(input)?)
+}
+
+/// Sum of the extrapolated next values.
+pub fn part1(histories: &[History]) -> Result<i64> {
+    sum_extrapolated(histories, DifferenceTable::next)
+}
+
+/// Sum of the extrapolated previous values.
+pub fn part2(histories: &[History]) -> Result<i64> {
+    sum_extrapolated(histories, DifferenceTable::previous)
+}
+
+fn sum_extrapolated(histories: &[History], extrapolate: fn(&DifferenceTable) -> Option<i64>) -> Result<i64> {
+    histories.iter().enumerate().try_fold(0i64, |total, (i, history)| {
+        DifferenceTable::new(history)
+            .as_ref()
+            .and_then(extrapolate)
+            .and_then(|value| total.checked_add(value))
+            .ok_or_else(|| anyhow!("History {} does not fit in 64 bits once extrapolated", i + 1))
+    })
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<History>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_history(i + 1, line))
+        .collect()
+}
+
+fn parse_history(line_no: usize, line: &str) -> Result<History, ParseError> {
+    if line.trim().is_empty() {
+        return Err(ParseError::at(DAY, line_no, line, line, "at least one value"));
+    }
+    line.split_whitespace()
+        .map(|val| {
+            val.parse::<i64>()
+                .map_err(|_| ParseError::at_token(DAY, line_no, line, val, "a number"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 114);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), 2);
+    }
+
+    #[test]
+    fn constant_and_negative_histories() {
+        assert_eq!(solve_part1("4").unwrap(), 4);
+        assert_eq!(solve_part1("5 5 5").unwrap(), 5);
+        assert_eq!(solve_part1("-1 -3 -5").unwrap(), -7);
+        assert_eq!(solve_part2("-1 -3 -5").unwrap(), 1);
+    }
+
+    #[test]
+    fn overflow_names_the_history() {
+        let err = solve_part1("1 2 3\n-9223372036854775808 9223372036854775807").unwrap_err();
+        assert_eq!(err.to_string(), "History 2 does not fit in 64 bits once extrapolated");
+        assert!(solve_part1("9223372036854775806 9223372036854775807").is_err());
+        assert!(solve_part2("9223372036854775806 9223372036854775807").is_ok());
+    }
+}
@@ -0,0 +1,350 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+
+use crate::{
+    animation::Animate,
+    error::ParseError,
+    geometry::Point,
+    grid::Grid,
+    pipes::{self, PipeError},
+    polygon,
+    render::{Colour, Mode, Render},
+    solution::Day,
+};
+
+const DAY: u8 = 10;
+
+pub struct Day10;
+
+impl Day for Day10 {
+    const DAY: u8 = DAY;
+    type Input = TileMap;
+
+    fn parse(input: &str) -> Result<TileMap> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &TileMap) -> Result<impl Display> {
+        part1(input)
+    }
+
+    fn part2(input: &TileMap) -> Result<impl Display> {
+        part2(input)
+    }
+}
+
+pub use crate::pipes::{TileKind, TileMap};
+
+/// How part 2 counts the tiles enclosed by the loop.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AreaMethod {
+    /// Shoelace formula for the area, then Pick's theorem for the tiles inside.
+    #[default]
+    Pick,
+    /// Scan each row, flipping between outside and inside on loop tiles that connect north.
+    Scanline,
+}
+
+impl AreaMethod {
+    pub const ALL: [AreaMethod; 2] = [AreaMethod::Pick, AreaMethod::Scanline];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Scanline => "scanline",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|method| method.name() == name)
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<i64> {
+    part1(&parse_input(input)?)
+}
+
+pub fn solve_part2(input: &str) -> Result<usize> {
+    part2(&parse_input(input)?)
+}
+
+pub fn solve_part2_with(input: &str, method: AreaMethod) -> Result<usize> {
+    part2_with(&parse_input(input)?, method)
+}
+
+/// Steps from the start to the farthest point of the loop.
+pub fn part1(tile_map: &TileMap) -> Result<i64> {
+    let (_, steps) = pipes::start_loop(tile_map)?.farthest();
+    Ok(steps as i64)
+}
+
+/// Number of tiles enclosed by the loop.
+pub fn part2(tile_map: &TileMap) -> Result<usize> {
+    part2_with(tile_map, AreaMethod::default())
+}
+
+pub fn part2_with(tile_map: &TileMap, method: AreaMethod) -> Result<usize> {
+    let path = pipes::start_loop(tile_map)?;
+    Ok(match method {
+        AreaMethod::Pick => polygon::interior_count(path.path()) as usize,
+        AreaMethod::Scanline => polygon::scanline_interior(path.path()).len(),
+    })
+}
+
+pub fn parse_input(input: &str) -> Result<TileMap, ParseError> {
+    let tiles = Grid::parse(input, |c| TileKind::try_from(c).ok())
+        .map_err(|err| err.to_parse_error(DAY, input, "a tile (|-LJ7F.S)"))?;
+
+    let starts: Vec<Point> = tiles
+        .iter()
+        .filter(|(_, tile)| matches!(tile, TileKind::Start))
+        .map(|(pos, _)| pos)
+        .collect();
+    let start = match starts.as_slice() {
+        [] => return Err(ParseError::end_of_input(DAY, input, "a start tile 'S'")),
+        [start] => *start,
+        [_, second, ..] => {
+            return Err(ParseError::new(DAY, second.row as usize + 1, second.col as usize + 1, "a single start tile"))
+        }
+    };
+
+    Ok(TileMap { tiles, start })
+}
+
+/// The pipes with the loop in green, its start in red and the tiles it encloses as a blue 'I'.
+/// Pipes off the loop are grey.
+pub struct LoopView {
+    tiles: Grid<TileKind>,
+    start: Point,
+    on_loop: Grid<bool>,
+    inside: Grid<bool>,
+}
+
+impl LoopView {
+    pub fn new(tile_map: &TileMap) -> Result<Self, PipeError> {
+        let tiles = tile_map.resolved()?;
+        let path = pipes::start_loop(tile_map)?;
+        let mut on_loop = tiles.map(|_| false);
+        for pos in path.path() {
+            on_loop[*pos] = true;
+        }
+        let mut inside = tiles.map(|_| false);
+        for pos in polygon::scanline_interior(path.path()) {
+            inside[pos] = true;
+        }
+        Ok(Self {
+            tiles,
+            start: tile_map.start,
+            on_loop,
+            inside,
+        })
+    }
+}
+
+impl Render for LoopView {
+    type Cell = TileKind;
+
+    fn grid(&self) -> &Grid<TileKind> {
+        &self.tiles
+    }
+
+    fn glyph(&self, pos: Point, tile: &TileKind) -> char {
+        if self.inside[pos] {
+            'I'
+        } else {
+            char::from(tile)
+        }
+    }
+
+    fn colour(&self, pos: Point, _tile: &TileKind) -> Option<Colour> {
+        if pos == self.start {
+            Some(Colour::Red)
+        } else if self.on_loop[pos] {
+            Some(Colour::Green)
+        } else if self.inside[pos] {
+            Some(Colour::Blue)
+        } else {
+            Some(Colour::Grey)
+        }
+    }
+}
+
+/// The walk around the loop from the start, one tile per step: tiles already walked in green,
+/// the current one in yellow, the start in red and everything else grey.
+pub struct LoopWalk {
+    tiles: Grid<TileKind>,
+    path: Vec<Point>,
+    /// Step at which each tile of the loop is reached.
+    reached: Grid<Option<usize>>,
+    step: usize,
+}
+
+impl LoopWalk {
+    pub fn new(tile_map: &TileMap) -> Result<Self, PipeError> {
+        let tiles = tile_map.resolved()?;
+        let path = pipes::start_loop(tile_map)?.path().to_vec();
+        let mut reached = tiles.map(|_| None);
+        for (step, pos) in path.iter().enumerate() {
+            reached[*pos] = Some(step);
+        }
+        Ok(Self {
+            tiles,
+            path,
+            reached,
+            step: 0,
+        })
+    }
+
+    pub fn position(&self) -> Point {
+        self.path[self.step % self.path.len()]
+    }
+}
+
+impl Render for LoopWalk {
+    type Cell = TileKind;
+
+    fn grid(&self) -> &Grid<TileKind> {
+        &self.tiles
+    }
+
+    fn glyph(&self, _pos: Point, tile: &TileKind) -> char {
+        char::from(tile)
+    }
+
+    fn colour(&self, pos: Point, _tile: &TileKind) -> Option<Colour> {
+        match self.reached[pos] {
+            _ if pos == self.position() => Some(Colour::Yellow),
+            Some(0) => Some(Colour::Red),
+            Some(step) if step < self.step => Some(Colour::Green),
+            _ => Some(Colour::Grey),
+        }
+    }
+}
+
+/// Ends back on the start, once round the whole loop.
+impl Animate for LoopWalk {
+    fn frame(&self, mode: Mode) -> String {
+        format!("Step {} of {}\n{}", self.step, self.path.len(), self.render(mode))
+    }
+
+    fn is_done(&self) -> bool {
+        self.step == self.path.len()
+    }
+
+    fn advance(&mut self) {
+        self.step += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....
+";
+
+    const EXAMPLE_1: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+
+    const ENCLOSED_4: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+";
+
+    const ENCLOSED_8: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    const ENCLOSED_10: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(solve_part1(SQUARE).unwrap(), 4);
+        assert_eq!(solve_part1(EXAMPLE_1).unwrap(), 8);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(solve_part2(SQUARE).unwrap(), 1);
+        assert_eq!(solve_part2(ENCLOSED_4).unwrap(), 4);
+        assert_eq!(solve_part2(ENCLOSED_8).unwrap(), 8);
+        assert_eq!(solve_part2(ENCLOSED_10).unwrap(), 10);
+    }
+
+    #[test]
+    fn area_methods_agree() {
+        for example in [SQUARE, EXAMPLE_1, ENCLOSED_4, ENCLOSED_8, ENCLOSED_10] {
+            let map = parse_input(example).unwrap();
+            let counts: Vec<usize> = AreaMethod::ALL.iter().map(|method| part2_with(&map, *method).unwrap()).collect();
+            assert!(counts.iter().all(|count| *count == counts[0]), "{:?}", counts);
+        }
+        assert_eq!(AreaMethod::from_name("scanline"), Some(AreaMethod::Scanline));
+        assert_eq!(AreaMethod::from_name("rays"), None);
+    }
+
+    #[test]
+    fn view_marks_loop_and_inside() {
+        let view = LoopView::new(&parse_input(SQUARE).unwrap()).unwrap();
+        assert_eq!(view.render(Mode::Plain), "OOOOO\nO┌─┐O\nO│I│O\nO└─┘O\nOOOOO\n");
+        assert_eq!(view.colour(Point::new(1, 1), &TileKind::Start), Some(Colour::Red));
+        assert_eq!(view.colour(Point::new(2, 2), &TileKind::Empty), Some(Colour::Blue));
+    }
+
+    #[test]
+    fn walk_goes_once_round_the_loop() {
+        let mut walk = LoopWalk::new(&parse_input(SQUARE).unwrap()).unwrap();
+        assert_eq!(walk.position(), Point::new(1, 1));
+        let mut seen = vec![];
+        while !walk.is_done() {
+            walk.advance();
+            seen.push(walk.position());
+        }
+        assert_eq!(seen.len(), 8);
+        assert_eq!(seen.last(), Some(&Point::new(1, 1)));
+        assert_eq!(walk.frame(Mode::Plain), "Step 8 of 8\nOOOOO\nO┌─┐O\nO│O│O\nO└─┘O\nOOOOO\n");
+    }
+
+    #[test]
+    fn missing_start_is_rejected() {
+        let err = parse_input("F7\nLJ").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+}
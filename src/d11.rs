@@ -0,0 +1,214 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+
+use crate::{
+    error::ParseError,
+    geometry::Point,
+    grid::Grid,
+    render::{Colour, Render},
+    solution::Day,
+};
+
+const DAY: u8 = 11;
+
+pub struct Day11;
+
+impl Day for Day11 {
+    const DAY: u8 = DAY;
+    type Input = Image;
+
+    fn parse(input: &str) -> Result<Image> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Image) -> Result<impl Display> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Image) -> Result<impl Display> {
+        Ok(part2(input))
+    }
+}
+
+/// The image as read: '#' for a galaxy, '.' for empty space.
+pub type Image = Grid<char>;
+
+/// Galaxy positions once the image has been expanded.
+#[derive(Debug)]
+pub struct Space {
+    pub rows: u64,
+    pub cols: u64,
+    pub galaxies: Vec<Point>,
+}
+
+pub fn solve_part1(input: &str) -> Result<u64, ParseError> {
+    Ok(part1(&parse_input(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u64, ParseError> {
+    Ok(part2(&parse_input(input)?))
+}
+
+/// Sum of the distances between every pair of galaxies, empty rows and columns counting twice.
+pub fn part1(image: &Image) -> u64 {
+    sum_of_distances(image, 2)
+}
+
+/// Like [`part1`], but empty rows and columns are a million times wider.
+pub fn part2(image: &Image) -> u64 {
+    sum_of_distances(image, 1_000_000)
+}
+
+pub fn sum_of_distances(image: &Image, expansion: u64) -> u64 {
+    let space = expand_space(image, expansion);
+
+    unique_pairs(&space.galaxies).into_iter().map(|(gal1, gal2)| gal1.manhattan(*gal2)).sum()
+}
+
+pub fn parse_input(input: &str) -> Result<Image, ParseError> {
+    Grid::parse(input, |c| matches!(c, '.' | '#').then_some(c))
+        .map_err(|err| err.to_parse_error(DAY, input, "'.' or '#'"))
+}
+
+/// Finds the galaxies of `image`, with every empty row and column made `expansion` rows or
+/// columns wide.
+pub fn expand_space(image: &Image, expansion: u64) -> Space {
+    let row_maps = expanded_positions(empty_rows(image).into_iter(), expansion);
+    let col_maps = expanded_positions(empty_columns(image).into_iter(), expansion);
+
+    let galaxies = image
+        .iter()
+        .filter(|(_, c)| **c == '#')
+        .map(|(pos, _)| Point::new(row_maps[pos.row as usize], col_maps[pos.col as usize]))
+        .collect();
+
+    Space {
+        rows: row_maps.last().map_or(0, |r| r + 1) as u64,
+        cols: col_maps.last().map_or(0, |c| c + 1) as u64,
+        galaxies,
+    }
+}
+
+/// Whether each row of the image is free of galaxies.
+pub fn empty_rows(image: &Image) -> Vec<bool> {
+    image.iter_rows().map(|row| row.iter().all(|c| *c == '.')).collect()
+}
+
+/// Whether each column of the image is free of galaxies.
+pub fn empty_columns(image: &Image) -> Vec<bool> {
+    image.iter_columns().map(|mut col| col.all(|c| *c == '.')).collect()
+}
+
+/// The image with galaxies in yellow and the rows and columns that expand in blue.
+pub struct ExpansionView<'a> {
+    image: &'a Image,
+    empty_rows: Vec<bool>,
+    empty_cols: Vec<bool>,
+}
+
+impl<'a> ExpansionView<'a> {
+    pub fn new(image: &'a Image) -> Self {
+        Self {
+            image,
+            empty_rows: empty_rows(image),
+            empty_cols: empty_columns(image),
+        }
+    }
+}
+
+impl Render for ExpansionView<'_> {
+    type Cell = char;
+
+    fn grid(&self) -> &Grid<char> {
+        self.image
+    }
+
+    fn glyph(&self, _pos: Point, cell: &char) -> char {
+        *cell
+    }
+
+    fn colour(&self, pos: Point, cell: &char) -> Option<Colour> {
+        if *cell == '#' {
+            Some(Colour::Yellow)
+        } else if self.empty_rows[pos.row as usize] || self.empty_cols[pos.col as usize] {
+            Some(Colour::Blue)
+        } else {
+            None
+        }
+    }
+}
+
+/// New position of each row (or column), given which of them are empty.
+fn expanded_positions(empty: impl Iterator<Item = bool>, expansion: u64) -> Vec<i64> {
+    let mut offset: i64 = 0;
+    empty
+        .enumerate()
+        .map(|(i, is_empty)| {
+            if is_empty {
+                offset += expansion as i64 - 1;
+            }
+            i as i64 + offset
+        })
+        .collect()
+}
+
+fn unique_pairs(galaxies: &[Point]) -> Vec<(&Point, &Point)> {
+    let mut pairs = Vec::new();
+    for i in 0..galaxies.len().saturating_sub(1) {
+        for j in i + 1..galaxies.len() {
+            pairs.push((&galaxies[i], &galaxies[j]));
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(solve_part1(EXAMPLE).unwrap(), 374);
+    }
+
+    #[test]
+    fn larger_expansions() {
+        let image = parse_input(EXAMPLE).unwrap();
+        assert_eq!(sum_of_distances(&image, 10), 1030);
+        assert_eq!(sum_of_distances(&image, 100), 8410);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(solve_part2(EXAMPLE).unwrap(), 82000210);
+    }
+
+    #[test]
+    fn single_galaxy_has_no_pairs() {
+        assert_eq!(solve_part1("..\n.#").unwrap(), 0);
+    }
+
+    #[test]
+    fn view_marks_expanding_lines() {
+        let image = parse_input("#..\n...\n..#").unwrap();
+        assert_eq!((empty_rows(&image), empty_columns(&image)), (vec![false, true, false], vec![false, true, false]));
+        let view = ExpansionView::new(&image);
+        let colours: Vec<Option<Colour>> = image.iter().map(|(pos, c)| view.colour(pos, c)).collect();
+        let (y, b) = (Some(Colour::Yellow), Some(Colour::Blue));
+        assert_eq!(colours, [y, b, None, b, b, b, None, b, y]);
+    }
+}
@@ -0,0 +1,132 @@
+use std::{error, fmt};
+
+use nom::error::{VerboseError, VerboseErrorKind};
+
+/// Where and why an input could not be parsed. Lines and columns are 1-based.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Error pointing at `rest`, which must be a suffix of `line`.
+    pub fn at(day: u8, line_no: usize, line: &str, rest: &str, expected: impl Into<String>) -> Self {
+        Self::new(day, line_no, column_of(line, rest), expected)
+    }
+
+    /// Error pointing at `token`, which must be a subslice of `line` (e.g. from `split_whitespace`).
+    pub fn at_token(day: u8, line_no: usize, line: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        Self::at(day, line_no, line, &line[offset.min(line.len())..], expected)
+    }
+
+    /// Error for something missing once the whole input has been read.
+    pub fn end_of_input(day: u8, input: &str, expected: impl Into<String>) -> Self {
+        Self::new(day, input.lines().count() + 1, 1, expected)
+    }
+
+    /// Converts the error of a nom parser run on a single `line`. The innermost `context` label
+    /// becomes the expectation, falling back to the kind of the failing combinator.
+    pub fn from_nom(day: u8, line_no: usize, line: &str, err: nom::Err<VerboseError<&str>>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let rest = e.errors.first().map_or("", |(rest, _)| *rest);
+                let expected = e
+                    .errors
+                    .iter()
+                    .find_map(|(_, kind)| match kind {
+                        VerboseErrorKind::Context(ctx) => Some(ctx.to_string()),
+                        _ => None,
+                    })
+                    .or_else(|| e.errors.first().map(|(_, kind)| describe(kind)))
+                    .unwrap_or_else(|| "valid input".to_string());
+                Self::at(day, line_no, line, rest, expected)
+            }
+            nom::Err::Incomplete(_) => Self::new(day, line_no, line.chars().count() + 1, "more input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Day {:02} input, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+fn column_of(line: &str, rest: &str) -> usize {
+    let consumed = line.len().saturating_sub(rest.len());
+    line[..consumed].chars().count() + 1
+}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Char(c) => format!("'{}'", c),
+        VerboseErrorKind::Context(ctx) => ctx.to_string(),
+        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, error::context};
+
+    use super::*;
+
+    #[test]
+    fn columns_count_characters() {
+        let line = "Time: 7 x 30";
+        assert_eq!(ParseError::at(6, 1, line, "x 30", "a number").column, 9);
+        assert_eq!(ParseError::at(6, 1, "é x", "x", "a number").column, 3);
+    }
+
+    #[test]
+    fn tokens_point_at_their_position() {
+        let line = "41 48 8x 86";
+        let token = line.split_whitespace().nth(2).unwrap();
+        assert_eq!(ParseError::at_token(4, 3, line, token, "a number"), ParseError::new(4, 3, 7, "a number"));
+    }
+
+    #[test]
+    fn end_of_input_is_past_the_last_line() {
+        assert_eq!(ParseError::end_of_input(5, "seeds: 1\n\n", "a map").line, 3);
+    }
+
+    #[test]
+    fn nom_errors_use_the_context() {
+        let mut game = context::<_, VerboseError<&str>, _, _>("'Game '", tag("Game "));
+
+        let line = "Gaem 1: 3 blue";
+        let err = game(line).unwrap_err();
+        assert_eq!(ParseError::from_nom(2, 4, line, err), ParseError::new(2, 4, 1, "'Game '"));
+
+        let line = "Game 1: 3 blue; Game 2";
+        let err = game(&line[8..]).unwrap_err();
+        assert_eq!(ParseError::from_nom(2, 1, line, err).column, 9);
+    }
+
+    #[test]
+    fn display_names_day_and_position() {
+        assert_eq!(
+            ParseError::new(7, 12, 3, "a card label").to_string(),
+            "Day 07 input, line 12, column 3: expected a card label"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Position on a grid. Rows grow downwards (south) and columns to the right (east).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { row: 0, col: 0 };
+
+    pub const fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    /// The neighbouring point towards `dir`.
+    pub fn step(self, dir: impl Into<Direction8>) -> Self {
+        self + dir.into().offset()
+    }
+
+    /// Orthogonal neighbours, in the order of `Direction::ALL`.
+    pub fn neighbours4(self) -> [Point; 4] {
+        Direction::ALL.map(|dir| self.step(dir))
+    }
+
+    /// Orthogonal and diagonal neighbours, in the order of `Direction8::ALL`.
+    pub fn neighbours8(self) -> [Point; 8] {
+        Direction8::ALL.map(|dir| self.step(dir))
+    }
+
+    /// Steps needed to reach `other` moving orthogonally.
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+
+    /// Steps needed to reach `other` when diagonal moves are allowed too.
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.row.abs_diff(other.row).max(self.col.abs_diff(other.col))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Point {
+        Point::new(self.row * rhs, self.col * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.row, -self.col)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Point {
+        self.step(rhs)
+    }
+}
+
+impl Add<Direction8> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction8) -> Point {
+        self.step(rhs)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((row, col): (i64, i64)) -> Self {
+        Point::new(row, col)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise, starting north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+        }
+    }
+
+    /// Quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    /// Quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    /// Point one step away from the origin.
+    pub fn offset(self) -> Point {
+        Direction8::from(self).offset()
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise, starting north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|dir| *dir == self).unwrap()
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    /// Eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Eighth of a turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Point one step away from the origin.
+    pub fn offset(self) -> Point {
+        let (row, col) = match self {
+            Self::North => (-1, 0),
+            Self::NorthEast => (-1, 1),
+            Self::East => (0, 1),
+            Self::SouthEast => (1, 1),
+            Self::South => (1, 0),
+            Self::SouthWest => (1, -1),
+            Self::West => (0, -1),
+            Self::NorthWest => (-1, -1),
+        };
+        Point::new(row, col)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::North => Self::North,
+            Direction::East => Self::East,
+            Direction::South => Self::South,
+            Direction::West => Self::West,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(2, -3);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(1, 2));
+        assert_eq!(a - b, Point::new(3, -8));
+        assert_eq!(a * 3, Point::new(6, -9));
+        assert_eq!(-a, Point::new(-2, 3));
+
+        let mut c = a;
+        c += b;
+        c -= b;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, -1);
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!(b.manhattan(a), a.manhattan(b));
+    }
+
+    #[test]
+    fn steps_follow_rows_down_and_columns_right() {
+        let p = Point::ORIGIN;
+        assert_eq!(p + Direction::North, Point::new(-1, 0));
+        assert_eq!(p.step(Direction::East), Point::new(0, 1));
+        assert_eq!(p.step(Direction8::SouthWest), Point::new(1, -1));
+        assert!(p.neighbours8().iter().all(|n| n.chebyshev(p) == 1));
+        assert!(p.neighbours4().iter().all(|n| n.manhattan(p) == 1));
+    }
+
+    #[test]
+    fn turning() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(dir.offset() + dir.opposite().offset(), Point::ORIGIN);
+        }
+        for dir in Direction8::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!((0..4).fold(dir, |d, _| d.turn_right()), dir.opposite());
+            assert_eq!(dir.offset() + dir.opposite().offset(), Point::ORIGIN);
+        }
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert!(Direction8::from(Direction::West).turn_right().is_diagonal());
+    }
+}
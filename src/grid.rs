@@ -0,0 +1,257 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{error::ParseError, geometry::Point};
+
+/// Rectangular grid stored in row-major order. Points outside of it are rejected by the bounds
+/// checks rather than being invalid, so neighbours of the border can be computed freely.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+/// Why text could not be read as a grid. Lines and columns are 1-based.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GridError {
+    Empty,
+    Ragged { line: usize, width: usize, expected: usize },
+    Cell { line: usize, column: usize },
+}
+
+impl GridError {
+    /// Turns the error into the day's `ParseError`; `cell` describes what a valid cell is.
+    pub fn to_parse_error(&self, day: u8, input: &str, cell: &str) -> ParseError {
+        match *self {
+            Self::Empty => ParseError::end_of_input(day, input, "a row of cells"),
+            Self::Ragged { line, width, expected } => {
+                ParseError::new(day, line, width.min(expected) + 1, format!("{} cells per row", expected))
+            }
+            Self::Cell { line, column } => ParseError::new(day, line, column, cell),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(rows * cols, cells.len(), "a {}x{} grid needs {} cells", rows, cols, rows * cols);
+        Self { rows, cols, cells }
+    }
+
+    /// One row per line and one cell per character. `cell` rejects a character by returning `None`.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let cols = input.lines().next().ok_or(GridError::Empty)?.chars().count();
+
+        let mut rows = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for (row, line) in input.lines().enumerate() {
+            let mut width = 0;
+            for (col, c) in line.chars().enumerate() {
+                let value = cell(c).ok_or(GridError::Cell {
+                    line: row + 1,
+                    column: col + 1,
+                })?;
+                cells.push(value);
+                width += 1;
+            }
+            if width != cols {
+                return Err(GridError::Ragged {
+                    line: row + 1,
+                    width,
+                    expected: cols,
+                });
+            }
+            rows += 1;
+        }
+
+        Ok(Self::new(rows, cols, cells))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn in_bounds(&self, pos: Point) -> bool {
+        pos.row >= 0 && pos.col >= 0 && (pos.row as usize) < self.rows && (pos.col as usize) < self.cols
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.idx(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.idx(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        pos.neighbours4().into_iter().filter(|n| self.in_bounds(*n))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        pos.neighbours8().into_iter().filter(|n| self.in_bounds(*n))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| Point::new((i / cols) as i64, (i % cols) as i64))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.cols.max(1)).take(self.rows)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.rows, self.cols, self.cells.iter().map(f).collect())
+    }
+
+    fn idx(&self, pos: Point) -> Option<usize> {
+        self.in_bounds(pos)
+            .then(|| pos.row as usize * self.cols + pos.col as usize)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Rows become columns: the cell at (r, c) moves to (c, r).
+    pub fn transpose(&self) -> Self {
+        let cells = self.iter_columns().flat_map(|col| col.cloned()).collect();
+        Grid::new(self.cols, self.rows, cells)
+    }
+
+    /// Quarter turn clockwise: the first column, read bottom up, becomes the first row.
+    pub fn rotate_clockwise(&self) -> Self {
+        let cells = (0..self.cols)
+            .flat_map(|col| (0..self.rows).rev().map(move |row| (row, col)))
+            .map(|(row, col)| self.cells[row * self.cols + col].clone())
+            .collect();
+        Grid::new(self.cols, self.rows, cells)
+    }
+
+    /// Quarter turn counter-clockwise: the last column, read top down, becomes the first row.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let cells = (0..self.cols)
+            .rev()
+            .flat_map(|col| (0..self.rows).map(move |row| (row, col)))
+            .map(|(row, col)| self.cells[row * self.cols + col].clone())
+            .collect();
+        Grid::new(self.cols, self.rows, cells)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, self.rows, self.cols))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, rows, cols))
+    }
+}
+
+/// One line per row, with no separator between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let grid = letters();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows_and_bad_cells() {
+        assert_eq!(Grid::parse("", Some), Err(GridError::Empty));
+        assert_eq!(
+            Grid::parse("ab\nabc", Some),
+            Err(GridError::Ragged { line: 2, width: 3, expected: 2 })
+        );
+        assert_eq!(
+            Grid::parse("..\n.#", |c| (c == '.').then_some(())),
+            Err(GridError::Cell { line: 2, column: 2 })
+        );
+    }
+
+    #[test]
+    fn get_is_bounds_checked() {
+        let grid = letters();
+        assert_eq!(grid.get(Point::new(1, 2)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = letters();
+        assert_eq!(grid.neighbours4(Point::ORIGIN).collect::<Vec<_>>(), [Point::new(0, 1), Point::new(1, 0)]);
+        assert_eq!(grid.neighbours8(Point::new(0, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = letters();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.iter_columns().count(), 3);
+    }
+
+    #[test]
+    fn transpose_and_rotate() {
+        let grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+pub struct Request<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub headers: Vec<(&'a str, String)>,
+    pub body: Option<String>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Anything able to perform a single HTTP request. The site client only talks to this trait so
+/// tests and local stand-ins can swap the transport.
+pub trait HttpClient {
+    fn send(&self, request: &Request) -> Result<Response>;
+}
+
+/// Picks a backend for the URL scheme: plain `http://` is spoken directly over TCP, everything
+/// else goes through the system `curl`.
+pub fn client_for(url: &str) -> Box<dyn HttpClient> {
+    if url.starts_with("http://") {
+        Box::new(TcpClient)
+    } else {
+        Box::new(CurlClient)
+    }
+}
+
+/// Minimal HTTP/1.1 client without TLS, meant for local mock servers.
+pub struct TcpClient;
+
+impl HttpClient for TcpClient {
+    fn send(&self, request: &Request) -> Result<Response> {
+        let rest = request
+            .url
+            .strip_prefix("http://")
+            .ok_or_else(|| anyhow!("TcpClient only supports http:// URLs, got {}", request.url))?;
+        let (host, path) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], &rest[idx..]),
+            None => (rest, "/"),
+        };
+        let addr = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+
+        let mut stream =
+            TcpStream::connect(&addr).with_context(|| format!("Could not connect to {}", addr))?;
+        stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+
+        let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", request.method, path, host);
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        let body = request.body.as_deref().unwrap_or("");
+        if request.body.is_some() {
+            head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        }
+        head.push_str("\r\n");
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(body.as_bytes())?;
+
+        read_response(BufReader::new(stream))
+    }
+}
+
+fn read_response(mut reader: impl BufRead) -> Result<Response> {
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| anyhow!("Malformed status line '{}'", status_line.trim_end()))?;
+
+    let mut content_length = None;
+    let mut chunked = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse::<usize>().ok();
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            }
+        }
+    }
+
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size_line = String::new();
+            reader.read_line(&mut size_line)?;
+            let size_hex = size_line.trim().split(';').next().unwrap_or("");
+            let size = usize::from_str_radix(size_hex, 16)
+                .with_context(|| format!("Malformed chunk size '{}'", size_line.trim()))?;
+            if size == 0 {
+                break;
+            }
+            let mut chunk = vec![0; size];
+            reader.read_exact(&mut chunk)?;
+            body.extend(chunk);
+            let mut crlf = String::new();
+            reader.read_line(&mut crlf)?;
+        }
+    } else if let Some(length) = content_length {
+        body.resize(length, 0);
+        reader.read_exact(&mut body)?;
+    } else {
+        reader.read_to_end(&mut body)?;
+    }
+
+    Ok(Response {
+        status,
+        body: String::from_utf8(body).context("Response body is not UTF-8")?,
+    })
+}
+
+/// Delegates to the `curl` executable, which takes care of TLS.
+pub struct CurlClient;
+
+/// Separates the body from the status code appended by `--write-out`.
+const STATUS_MARKER: &str = "\n--curl-status:";
+
+impl HttpClient for CurlClient {
+    fn send(&self, request: &Request) -> Result<Response> {
+        // Headers and body go through a config file on stdin, so the session cookie never shows
+        // up in the process list.
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--request", request.method])
+            .args(["--write-out", &format!("{}%{{http_code}}", STATUS_MARKER)])
+            .args(["--config", "-"])
+            .arg(request.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = command.spawn().context("Could not run curl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(curl_config(request).as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!("curl failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        let stdout = String::from_utf8(output.stdout).context("Response body is not UTF-8")?;
+        let (body, status) = stdout
+            .rsplit_once(STATUS_MARKER)
+            .ok_or_else(|| anyhow!("curl did not report a status code"))?;
+
+        Ok(Response {
+            status: status.trim().parse().context("curl reported a malformed status code")?,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// The request's headers and body in curl's config file syntax.
+fn curl_config(request: &Request) -> String {
+    let mut config = String::new();
+    for (name, value) in &request.headers {
+        config.push_str(&format!("header = {}\n", curl_quote(&format!("{}: {}", name, value))));
+    }
+    if let Some(body) = &request.body {
+        config.push_str(&format!("data-binary = {}\n", curl_quote(body)));
+    }
+    config
+}
+
+/// A double quoted config value; curl reads backslash escapes inside the quotes.
+fn curl_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> Result<Response> {
+        read_response(raw.as_bytes())
+    }
+
+    #[test]
+    fn body_with_content_length() {
+        let response = parse("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello, and more").unwrap();
+        assert_eq!((response.status, response.body.as_str()), (200, "hello"));
+    }
+
+    #[test]
+    fn chunked_body() {
+        let raw = "HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n\
+                   4\r\nnot \r\n6;ext=1\r\nfound!\r\n0\r\n\r\n";
+        let response = parse(raw).unwrap();
+        assert_eq!((response.status, response.body.as_str()), (404, "not found!"));
+    }
+
+    #[test]
+    fn body_until_the_connection_closes() {
+        let response = parse("HTTP/1.0 200 OK\r\nServer: mock\r\n\r\n1 2 3\n").unwrap();
+        assert_eq!(response.body, "1 2 3\n");
+    }
+
+    #[test]
+    fn truncated_and_malformed_responses() {
+        assert!(parse("HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nshort").is_err());
+        assert!(parse("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n10\r\nshort").is_err());
+        assert!(parse("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n").is_err());
+        assert!(parse("garbage\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn curl_config_carries_headers_and_body() {
+        let request = Request {
+            method: "POST",
+            url: "https://example.com/answer",
+            headers: vec![("Cookie", "session=s3cr\"et".to_string())],
+            body: Some("level=1&answer=42".to_string()),
+        };
+        assert_eq!(
+            curl_config(&request),
+            "header = \"Cookie: session=s3cr\\\"et\"\ndata-binary = \"level=1&answer=42\"\n"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+/// Directory holding the `dNN-input.txt` files when `AOC_INPUTS` is not set, relative to the
+/// current directory so an installed binary does not point back into the build tree.
+const DEFAULT_INPUTS_DIR: &str = "src/bin/inputs";
+
+pub enum InputSource {
+    /// The day's file inside the inputs directory.
+    Day(u8),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// `-` stands for stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Self::Day(day) => {
+                let path = input_path(*day);
+                fs::read_to_string(&path).with_context(|| {
+                    format!(
+                        "Could not read input for day {} from {} (set AOC_INPUTS or pass --input <file>)",
+                        day,
+                        path.display()
+                    )
+                })
+            }
+            Self::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("Could not read input file {}", path.display())),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Could not read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// `$AOC_INPUTS`, or `src/bin/inputs` under the current directory.
+pub fn inputs_dir() -> PathBuf {
+    env::var_os("AOC_INPUTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR))
+}
+
+pub fn input_path(day: u8) -> PathBuf {
+    input_path_in(&inputs_dir(), day)
+}
+
+/// Where the input for `day` lives inside the inputs directory `dir`.
+pub fn input_path_in(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("d{:02}-input.txt", day))
+}
+
+/// Input source of a `dNNpM` binary, honouring `--input <file>` (or `-` for stdin).
+pub fn source_from_args(day: u8) -> Result<InputSource> {
+    Ok(bin_args(day, &[], &[])?.source)
+}
+
+/// Command line of a `dNNpM` binary: where to read the input from, the day's own switches
+/// that were given, and the values of its options.
+pub struct BinArgs {
+    pub source: InputSource,
+    switches: Vec<String>,
+    options: Vec<(String, String)>,
+}
+
+impl BinArgs {
+    pub fn has(&self, switch: &str) -> bool {
+        self.switches.iter().any(|s| s == switch)
+    }
+
+    /// Value given after `option`, the last one if it was repeated.
+    pub fn value(&self, option: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(name, _)| name == option)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Like [`source_from_args`], also accepting any of `switches` (e.g. `--explain`) and of
+/// `options` followed by a value, in any order.
+pub fn bin_args(day: u8, switches: &[&str], options: &[&str]) -> Result<BinArgs> {
+    let usage = || {
+        let extra: String = switches
+            .iter()
+            .map(|s| format!(" [{}]", s))
+            .chain(options.iter().map(|o| format!(" [{} <value>]", o)))
+            .collect();
+        format!("Usage: d{:02}pN [--input <file>|-]{}", day, extra)
+    };
+
+    let mut source = InputSource::Day(day);
+    let mut given = vec![];
+    let mut values = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => match args.next() {
+                Some(value) => source = InputSource::from_arg(&value),
+                None => bail!(usage()),
+            },
+            _ if switches.contains(&arg.as_str()) => given.push(arg),
+            _ if options.contains(&arg.as_str()) => match args.next() {
+                Some(value) => values.push((arg, value)),
+                None => bail!(usage()),
+            },
+            _ => bail!(usage()),
+        }
+    }
+
+    Ok(BinArgs {
+        source,
+        switches: given,
+        options: values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dash_means_stdin() {
+        assert!(matches!(InputSource::from_arg("-"), InputSource::Stdin));
+        assert!(matches!(InputSource::from_arg("d01.txt"), InputSource::File(path) if path.to_str() == Some("d01.txt")));
+    }
+
+    #[test]
+    fn reads_a_file() {
+        let path = env::temp_dir().join(format!("advent2023-input-{}.txt", std::process::id()));
+        fs::write(&path, "1abc2\n").unwrap();
+        assert_eq!(InputSource::File(path.clone()).read().unwrap(), "1abc2\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn missing_inputs_are_named() {
+        let err = InputSource::File(PathBuf::from("no/such/file.txt")).read().unwrap_err();
+        assert!(format!("{:#}", err).contains("no/such/file.txt"), "{:#}", err);
+
+        let err = InputSource::Day(99).read().unwrap_err();
+        assert!(format!("{:#}", err).contains("d99-input.txt"), "{:#}", err);
+    }
+
+    #[test]
+    fn default_inputs_are_found_from_the_current_directory() {
+        assert!(Path::new(DEFAULT_INPUTS_DIR).is_relative());
+        if env::var_os("AOC_INPUTS").is_none() {
+            assert_eq!(input_path(1), Path::new("src/bin/inputs/d01-input.txt"));
+        }
+    }
+}
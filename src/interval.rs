@@ -0,0 +1,538 @@
+use std::fmt;
+
+/// Half-open range of integers `start..end`. Empty when `end <= start`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    pub fn from_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.start.abs_diff(self.end)
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Set of integers stored as sorted, disjoint and non-touching intervals. Every constructor
+/// normalizes, so two sets with the same members compare equal.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts the intervals, drops the empty ones and merges those that overlap or touch.
+    pub fn from_intervals(intervals: impl IntoIterator<Item = Interval>) -> Self {
+        let mut sorted: Vec<Interval> = intervals.into_iter().filter(|iv| !iv.is_empty()).collect();
+        sorted.sort_by_key(|iv| iv.start);
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(sorted.len());
+        for iv in sorted {
+            match merged.last_mut() {
+                Some(last) if iv.start <= last.end => last.end = last.end.max(iv.end),
+                _ => merged.push(iv),
+            }
+        }
+
+        Self { intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|iv| iv.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|iv| iv.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let idx = self.intervals.partition_point(|iv| iv.end <= value);
+        self.intervals.get(idx).is_some_and(|iv| iv.contains(value))
+    }
+
+    /// Whether every member of `interval` is in the set.
+    pub fn contains_interval(&self, interval: &Interval) -> bool {
+        if interval.is_empty() {
+            return true;
+        }
+        let idx = self.intervals.partition_point(|iv| iv.end <= interval.start);
+        self.intervals
+            .get(idx)
+            .is_some_and(|iv| iv.start <= interval.start && interval.end <= iv.end)
+    }
+
+    pub fn is_subset(&self, other: &IntervalSet) -> bool {
+        self.intervals.iter().all(|iv| other.contains_interval(iv))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        Self::from_intervals(self.intervals.iter().chain(&other.intervals).copied())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let mut result = vec![];
+
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if let Some(overlap) = a[i].intersection(&b[j]) {
+                result.push(overlap);
+            }
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals: result }
+    }
+
+    /// Members of `self` that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let cuts = &other.intervals;
+        let mut result = vec![];
+
+        let mut first_cut = 0;
+        for iv in &self.intervals {
+            while first_cut < cuts.len() && cuts[first_cut].end <= iv.start {
+                first_cut += 1;
+            }
+
+            let mut start = iv.start;
+            for cut in cuts[first_cut..].iter().take_while(|cut| cut.start < iv.end) {
+                if start < cut.start {
+                    result.push(Interval::new(start, cut.start));
+                }
+                start = start.max(cut.end);
+            }
+            if start < iv.end {
+                result.push(Interval::new(start, iv.end));
+            }
+        }
+
+        Self { intervals: result }
+    }
+
+    pub fn shift(&self, offset: i64) -> IntervalSet {
+        Self {
+            intervals: self.intervals.iter().map(|iv| iv.shift(offset)).collect(),
+        }
+    }
+
+    /// Applies a piecewise translation: members inside a piece's interval move by that piece's
+    /// offset, members outside every piece stay where they are. Pieces must not overlap.
+    pub fn map_piecewise(&self, pieces: &[(Interval, i64)]) -> IntervalSet {
+        let mut moved = vec![];
+        let mut untouched = self.clone();
+
+        for (piece, offset) in pieces {
+            let piece = IntervalSet::from(*piece);
+            moved.extend(self.intersection(&piece).shift(*offset).intervals);
+            untouched = untouched.difference(&piece);
+        }
+
+        Self::from_intervals(moved.into_iter().chain(untouched.intervals))
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        Self::from_intervals([interval])
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        Self::from_intervals(iter)
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<String> = self.intervals.iter().map(Interval::to_string).collect();
+        write!(f, "{{{}}}", parts.join(", "))
+    }
+}
+
+/// Function on the integers that adds a constant offset on each of a sorted list of segments.
+/// Stored as the start of every segment with its offset; the first segment starts at `i64::MIN`
+/// and the last one runs up to `i64::MAX`. Both unbounded segments are identities.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PiecewiseMap {
+    breaks: Vec<(i64, i64)>,
+}
+
+impl Default for PiecewiseMap {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self {
+            breaks: vec![(i64::MIN, 0)],
+        }
+    }
+
+    /// Builds the map from (interval, offset) pieces; values outside every piece map to
+    /// themselves. Where pieces overlap, the first one listed wins.
+    pub fn from_pieces(pieces: &[(Interval, i64)]) -> Self {
+        let mut covered = IntervalSet::new();
+        let mut segments = vec![];
+        for (interval, offset) in pieces {
+            let piece = IntervalSet::from(*interval);
+            segments.extend(piece.difference(&covered).iter().map(|iv| (*iv, *offset)));
+            covered = covered.union(&piece);
+        }
+        segments.sort_by_key(|(iv, _)| iv.start);
+
+        let mut breaks = vec![(i64::MIN, 0)];
+        for (iv, offset) in segments {
+            breaks.push((iv.start, offset));
+            breaks.push((iv.end, 0));
+        }
+        Self::normalized(breaks)
+    }
+
+    /// Sorts the breaks, keeping the last of equal starts, and merges neighbours with the
+    /// same offset.
+    fn normalized(mut breaks: Vec<(i64, i64)>) -> Self {
+        breaks.sort_by_key(|(start, _)| *start);
+
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(breaks.len());
+        for (start, offset) in breaks {
+            if let Some(last) = merged.last_mut() {
+                if last.0 == start {
+                    *last = (start, offset);
+                    continue;
+                }
+            }
+            merged.push((start, offset));
+        }
+        merged.dedup_by(|next, prev| next.1 == prev.1);
+
+        Self { breaks: merged }
+    }
+
+    pub fn offset_at(&self, value: i64) -> i64 {
+        let idx = self.breaks.partition_point(|(start, _)| *start <= value);
+        self.breaks[idx - 1].1
+    }
+
+    /// Image of `value`, found with a single binary search.
+    pub fn apply(&self, value: i64) -> i64 {
+        value + self.offset_at(value)
+    }
+
+    /// Every segment with its offset, in order. The last one ends at `i64::MAX`.
+    pub fn segments(&self) -> impl Iterator<Item = (Interval, i64)> + '_ {
+        let ends = self.breaks.iter().skip(1).map(|(start, _)| *start).chain([i64::MAX]);
+        self.breaks
+            .iter()
+            .zip(ends)
+            .map(|((start, offset), end)| (Interval::new(*start, end), *offset))
+    }
+
+    /// The map applying `self` first and `next` on its result.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut starts: Vec<i64> = self.breaks.iter().map(|(start, _)| *start).collect();
+        for (segment, offset) in self.segments() {
+            // Values of this segment whose image lands on one of `next`'s breaks.
+            let preimages = next.breaks[1..]
+                .iter()
+                .filter_map(|(start, _)| start.checked_sub(offset))
+                .filter(|value| segment.contains(*value));
+            starts.extend(preimages);
+        }
+
+        let breaks = starts
+            .into_iter()
+            .map(|start| {
+                let offset = self.offset_at(start);
+                (start, offset + next.offset_at(start + offset))
+            })
+            .collect();
+        Self::normalized(breaks)
+    }
+
+    /// The map undoing `self`, if `self` is a bijection (its segments' images tile the integers).
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let mut images: Vec<(Interval, i64)> = self
+            .segments()
+            .map(|(segment, offset)| (segment.shift(offset), -offset))
+            .collect();
+        images.sort_by_key(|(iv, _)| iv.start);
+
+        let tiles = images.first().is_some_and(|(iv, _)| iv.start == i64::MIN)
+            && images.windows(2).all(|pair| pair[0].0.end == pair[1].0.start)
+            && images.last().is_some_and(|(iv, _)| iv.end == i64::MAX);
+        if !tiles {
+            return None;
+        }
+
+        Some(Self::normalized(images.into_iter().map(|(iv, offset)| (iv.start, offset)).collect()))
+    }
+
+    /// Member of `set` with the lowest image, together with that image.
+    pub fn min_image(&self, set: &IntervalSet) -> Option<(i64, i64)> {
+        self.segments()
+            .filter_map(|(segment, offset)| {
+                let lowest = set.intersection(&IntervalSet::from(segment)).min()?;
+                Some((lowest, lowest + offset))
+            })
+            .min_by_key(|(_, image)| *image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Members are checked one by one over this window, which every generated set fits into.
+    const WINDOW: std::ops::Range<i64> = -5..50;
+
+    /// Small xorshift generator so the property tests are reproducible without extra crates.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+
+        fn interval(&mut self) -> Interval {
+            let start = self.below(40) as i64;
+            Interval::from_len(start, self.below(8) as i64)
+        }
+
+        fn set(&mut self) -> IntervalSet {
+            let count = self.below(5);
+            (0..count).map(|_| self.interval()).collect()
+        }
+    }
+
+    fn for_random_sets(mut check: impl FnMut(&IntervalSet, &IntervalSet)) {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let (a, b) = (rng.set(), rng.set());
+            check(&a, &b);
+        }
+    }
+
+    fn assert_normalized(set: &IntervalSet) {
+        for iv in set.intervals() {
+            assert!(!iv.is_empty(), "{} holds an empty interval", set);
+        }
+        for pair in set.intervals().windows(2) {
+            assert!(pair[0].end < pair[1].start, "{} is not merged", set);
+        }
+    }
+
+    #[test]
+    fn from_intervals_merges_overlapping_and_touching() {
+        let set = IntervalSet::from_intervals([
+            Interval::new(10, 12),
+            Interval::new(0, 5),
+            Interval::new(5, 7),
+            Interval::new(3, 4),
+            Interval::new(20, 20),
+        ]);
+        assert_eq!(set.intervals(), [Interval::new(0, 7), Interval::new(10, 12)]);
+        assert_eq!(set.len(), 9);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(11)));
+    }
+
+    #[test]
+    fn map_piecewise_moves_covered_parts_only() {
+        let set = IntervalSet::from(Interval::new(0, 30));
+        let pieces = [(Interval::new(5, 10), 100), (Interval::new(20, 25), -20)];
+        // [20, 25) lands on [0, 5), which was left untouched anyway.
+        let expected = IntervalSet::from_intervals([
+            Interval::new(0, 5),
+            Interval::new(10, 20),
+            Interval::new(25, 30),
+            Interval::new(105, 110),
+        ]);
+        assert_eq!(set.map_piecewise(&pieces), expected);
+    }
+
+    #[test]
+    fn operations_match_membership() {
+        for_random_sets(|a, b| {
+            let (union, inter, diff) = (a.union(b), a.intersection(b), a.difference(b));
+            for set in [&union, &inter, &diff] {
+                assert_normalized(set);
+            }
+            for x in WINDOW {
+                let (in_a, in_b) = (a.contains(x), b.contains(x));
+                assert_eq!(union.contains(x), in_a || in_b, "{} | {} at {}", a, b, x);
+                assert_eq!(inter.contains(x), in_a && in_b, "{} & {} at {}", a, b, x);
+                assert_eq!(diff.contains(x), in_a && !in_b, "{} - {} at {}", a, b, x);
+            }
+        });
+    }
+
+    #[test]
+    fn algebraic_laws() {
+        for_random_sets(|a, b| {
+            assert_eq!(a.union(b), b.union(a));
+            assert_eq!(a.intersection(b), b.intersection(a));
+            assert_eq!(a.difference(b).union(&a.intersection(b)), *a);
+            assert!(a.difference(b).intersection(b).is_empty());
+            assert_eq!(a.union(b).len() + a.intersection(b).len(), a.len() + b.len());
+            assert!(a.intersection(b).is_subset(a));
+            assert!(a.is_subset(&a.union(b)));
+        });
+    }
+
+    #[test]
+    fn contains_interval_matches_membership() {
+        let mut rng = Rng(42);
+        for_random_sets(|a, _| {
+            let iv = rng.interval();
+            let expected = (iv.start..iv.end).all(|x| a.contains(x));
+            assert_eq!(a.contains_interval(&iv), expected, "{} in {}", iv, a);
+        });
+    }
+
+    #[test]
+    fn map_piecewise_matches_pointwise_translation() {
+        let mut rng = Rng(7);
+        for_random_sets(|a, _| {
+            // Two disjoint pieces: one in [0, 20), one in [20, 40).
+            let first = Interval::new(rng.below(10) as i64, 10 + rng.below(10) as i64);
+            let second = Interval::new(20 + rng.below(10) as i64, 30 + rng.below(10) as i64);
+            let pieces = [(first, 100), (second, -50)];
+
+            let translate = |x: i64| {
+                pieces
+                    .iter()
+                    .find(|(iv, _)| iv.contains(x))
+                    .map_or(x, |(_, offset)| x + offset)
+            };
+            let expected: IntervalSet = WINDOW
+                .filter(|x| a.contains(*x))
+                .map(|x| Interval::from_len(translate(x), 1))
+                .collect();
+
+            let mapped = a.map_piecewise(&pieces);
+            assert_normalized(&mapped);
+            assert_eq!(mapped, expected, "{} through {:?}", a, pieces);
+        });
+    }
+
+    fn random_map(rng: &mut Rng) -> PiecewiseMap {
+        let count = rng.below(4) as usize;
+        let pieces: Vec<(Interval, i64)> = (0..count)
+            .map(|_| (rng.interval(), rng.below(30) as i64 - 15))
+            .collect();
+        PiecewiseMap::from_pieces(&pieces)
+    }
+
+    #[test]
+    fn piecewise_map_first_piece_wins() {
+        let map = PiecewiseMap::from_pieces(&[(Interval::new(0, 10), 5), (Interval::new(5, 15), 100)]);
+        assert_eq!([-1, 0, 9, 10, 14, 15].map(|x| map.apply(x)), [-1, 5, 14, 110, 114, 15]);
+    }
+
+    #[test]
+    fn composition_matches_applying_in_turn() {
+        let mut rng = Rng(1234);
+        for _ in 0..300 {
+            let (first, second) = (random_map(&mut rng), random_map(&mut rng));
+            let composed = first.then(&second);
+            for x in WINDOW {
+                assert_eq!(composed.apply(x), second.apply(first.apply(x)), "{:?} then {:?} at {}", first, second, x);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_undoes_bijections_only() {
+        let swap = PiecewiseMap::from_pieces(&[(Interval::new(0, 5), 10), (Interval::new(10, 15), -10)]);
+        let inverse = swap.inverse().unwrap();
+        for x in WINDOW {
+            assert_eq!(inverse.apply(swap.apply(x)), x);
+        }
+
+        let collide = PiecewiseMap::from_pieces(&[(Interval::new(0, 5), 10)]);
+        assert_eq!(collide.inverse(), None);
+    }
+
+    #[test]
+    fn min_image_matches_brute_force() {
+        let mut rng = Rng(99);
+        for_random_sets(|a, _| {
+            let map = random_map(&mut rng);
+            let expected = WINDOW
+                .filter(|x| a.contains(*x))
+                .map(|x| map.apply(x))
+                .min();
+            assert_eq!(map.min_image(a).map(|(_, image)| image), expected);
+            if let Some((value, image)) = map.min_image(a) {
+                assert!(a.contains(value));
+                assert_eq!(map.apply(value), image);
+            }
+        });
+    }
+}
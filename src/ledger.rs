@@ -0,0 +1,256 @@
+use std::{
+    env, fmt, fs,
+    io::{ErrorKind, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{input::inputs_dir, solution::Part};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+            Self::Incorrect => "incorrect",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            "incorrect" => Ok(Self::Incorrect),
+            _ => Err(format!("Unknown verdict '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Entry {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// What the ledger knows about an answer produced by a solution.
+pub enum Check {
+    /// No correct answer has been recorded for this day and part yet.
+    Unverified,
+    Confirmed,
+    Regression { expected: String },
+}
+
+/// Every answer submitted so far, stored as tab separated `day part verdict answer` lines.
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Ledger at `$AOC_LEDGER`, or `ledger.tsv` next to the inputs.
+    pub fn default_path() -> PathBuf {
+        env::var_os("AOC_LEDGER")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| inputs_dir().join("ledger.tsv"))
+    }
+
+    pub fn load() -> Result<Self> {
+        Self::load_from(Self::default_path())
+    }
+
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Could not read ledger {}", path.display()))
+            }
+        };
+
+        let entries = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                parse_entry(line).with_context(|| format!("{}:{}: malformed ledger entry", path.display(), i + 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn entries(&self, day: u8, part: Part) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |e| e.day == day && e.part == part)
+    }
+
+    pub fn correct_answer(&self, day: u8, part: Part) -> Option<&str> {
+        self.entries(day, part)
+            .find(|e| e.verdict == Verdict::Correct)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// The verdict already recorded for this exact answer, if it was submitted before.
+    pub fn previous_verdict(&self, day: u8, part: Part, answer: &str) -> Option<Verdict> {
+        self.entries(day, part)
+            .find(|e| e.answer == answer)
+            .map(|e| e.verdict)
+    }
+
+    /// Uses earlier too-high/too-low hints to reject a numeric answer without submitting it.
+    pub fn out_of_known_bounds(&self, day: u8, part: Part, answer: &str) -> Option<Verdict> {
+        let value = answer.parse::<i128>().ok()?;
+        self.entries(day, part).find_map(|e| {
+            let bound = e.answer.parse::<i128>().ok()?;
+            match e.verdict {
+                Verdict::TooHigh if value >= bound => Some(Verdict::TooHigh),
+                Verdict::TooLow if value <= bound => Some(Verdict::TooLow),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Check {
+        match self.correct_answer(day, part) {
+            None => Check::Unverified,
+            Some(expected) if expected == answer => Check::Confirmed,
+            Some(expected) => Check::Regression {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn record(&mut self, entry: Entry) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Could not open ledger {}", self.path.display()))?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            entry.day, entry.part, entry.verdict, entry.answer
+        )?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+fn parse_entry(line: &str) -> Result<Entry> {
+    let mut fields = line.splitn(4, '\t');
+    let mut next = |name: &str| fields.next().ok_or_else(|| anyhow!("missing {}", name));
+
+    let day = next("day")?.parse()?;
+    let part = next("part")?
+        .parse::<u8>()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| anyhow!("invalid part"))?;
+    let verdict = next("verdict")?.parse().map_err(|e: String| anyhow!(e))?;
+    let answer = next("answer")?.to_string();
+
+    Ok(Entry {
+        day,
+        part,
+        answer,
+        verdict,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, part: Part, answer: &str, verdict: Verdict) -> Entry {
+        Entry {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    fn ledger(entries: Vec<Entry>) -> Ledger {
+        Ledger {
+            path: PathBuf::new(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn entries_round_trip_through_the_file() {
+        let path = env::temp_dir().join(format!("advent2023-ledger-{}.tsv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut written = Ledger::load_from(path.clone()).unwrap();
+        written.record(entry(3, Part::Two, "12 34", Verdict::TooLow)).unwrap();
+        written.record(entry(3, Part::Two, "99", Verdict::Correct)).unwrap();
+
+        let read = Ledger::load_from(path.clone()).unwrap();
+        let fields: Vec<(u8, Part, &str, Verdict)> = read
+            .entries(3, Part::Two)
+            .map(|e| (e.day, e.part, e.answer.as_str(), e.verdict))
+            .collect();
+        assert_eq!(fields, [(3, Part::Two, "12 34", Verdict::TooLow), (3, Part::Two, "99", Verdict::Correct)]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3\t2\ttoo-low\t12 34\n3\t2\tcorrect\t99\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn malformed_entries_are_rejected() {
+        let parsed = parse_entry("7\t1\tincorrect\tanswer\twith tab").unwrap();
+        assert_eq!((parsed.day, parsed.answer.as_str()), (7, "answer\twith tab"));
+        for line in ["7\t1\tincorrect", "x\t1\tcorrect\t5", "7\t3\tcorrect\t5", "7\t1\tmaybe\t5", "7"] {
+            assert!(parse_entry(line).is_err(), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn check_against_the_correct_answer() {
+        let ledger = ledger(vec![
+            entry(1, Part::One, "41", Verdict::Incorrect),
+            entry(1, Part::One, "42", Verdict::Correct),
+        ]);
+        assert!(matches!(ledger.check(1, Part::One, "42"), Check::Confirmed));
+        assert!(matches!(ledger.check(1, Part::One, "41"), Check::Regression { expected } if expected == "42"));
+        assert!(matches!(ledger.check(1, Part::Two, "42"), Check::Unverified));
+        assert_eq!(ledger.previous_verdict(1, Part::One, "41"), Some(Verdict::Incorrect));
+    }
+
+    #[test]
+    fn bounds_from_earlier_hints() {
+        let ledger = ledger(vec![
+            entry(5, Part::One, "100", Verdict::TooHigh),
+            entry(5, Part::One, "20", Verdict::TooLow),
+        ]);
+        assert_eq!(ledger.out_of_known_bounds(5, Part::One, "100"), Some(Verdict::TooHigh));
+        assert_eq!(ledger.out_of_known_bounds(5, Part::One, "150"), Some(Verdict::TooHigh));
+        assert_eq!(ledger.out_of_known_bounds(5, Part::One, "-3"), Some(Verdict::TooLow));
+        assert_eq!(ledger.out_of_known_bounds(5, Part::One, "50"), None);
+        assert_eq!(ledger.out_of_known_bounds(5, Part::One, "abc"), None);
+        assert_eq!(ledger.out_of_known_bounds(5, Part::Two, "150"), None);
+    }
+}
@@ -1,3 +1,32 @@
+pub mod animation;
+pub mod bench;
+pub mod bignum;
+pub mod bitmap;
+pub mod camel_cards;
+pub mod cycles;
+pub mod d01;
 pub mod d02;
+pub mod d03;
+pub mod d04;
 pub mod d05;
 pub mod d06;
+pub mod d07;
+pub mod d08;
+pub mod d09;
+pub mod d10;
+pub mod d11;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod interval;
+pub mod ledger;
+pub mod number_theory;
+pub mod oasis;
+pub mod pipes;
+pub mod polygon;
+pub mod render;
+pub mod runner;
+pub mod site;
+pub mod solution;
@@ -0,0 +1,265 @@
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or `None` if it does not fit in 64 bits. The LCM with zero is zero.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, where `g` is the non-negative GCD of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The numbers congruent to `residue` modulo `modulus`. The residue is kept in `0..modulus`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Congruence {
+    pub residue: u64,
+    pub modulus: u64,
+}
+
+impl Congruence {
+    pub fn new(residue: u64, modulus: u64) -> Self {
+        assert!(modulus > 0, "a congruence needs a positive modulus");
+        Self {
+            residue: residue % modulus,
+            modulus,
+        }
+    }
+
+    pub fn contains(&self, n: u64) -> bool {
+        n % self.modulus == self.residue
+    }
+
+    /// Smallest member that is at least `min`.
+    pub fn first_at_least(&self, min: u64) -> Option<u64> {
+        if min <= self.residue {
+            return Some(self.residue);
+        }
+        let periods = (min - self.residue).div_ceil(self.modulus);
+        periods.checked_mul(self.modulus)?.checked_add(self.residue)
+    }
+
+    /// Chinese Remainder Theorem: the numbers satisfying both congruences. The moduli need not
+    /// be coprime; `None` means no number satisfies both, or the combined modulus does not fit
+    /// in 64 bits.
+    pub fn combine(&self, other: &Congruence) -> Option<Congruence> {
+        let (a, m) = (self.residue as i128, self.modulus as i128);
+        let (b, n) = (other.residue as i128, other.modulus as i128);
+        let (g, x, _) = extended_gcd(m, n);
+        if (b - a) % g != 0 {
+            return None;
+        }
+
+        // a + m * k with m * k = b - a (mod n), so k = (b - a) / g * x (mod n / g).
+        let step = n / g;
+        // Both factors are below 2^64, so their product fits in a u128.
+        let k = ((b - a) / g).rem_euclid(step) as u128 * x.rem_euclid(step) as u128 % step as u128;
+        let k = k as i128;
+        let modulus = m.checked_mul(step)?;
+        let residue = (a + m * k).rem_euclid(modulus);
+        Some(Congruence::new(u64::try_from(residue).ok()?, u64::try_from(modulus).ok()?))
+    }
+}
+
+/// Combines every congruence with [`Congruence::combine`]; an empty list allows any number.
+pub fn crt(congruences: impl IntoIterator<Item = Congruence>) -> Option<Congruence> {
+    congruences
+        .into_iter()
+        .try_fold(Congruence::new(0, 1), |acc, c| acc.combine(&c))
+}
+
+/// Exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "a rational needs a non-zero denominator");
+        let (g, _, _) = extended_gcd(numer, denom);
+        let sign = denom.signum();
+        Self {
+            numer: sign * numer / g,
+            denom: sign * denom / g,
+        }
+    }
+
+    pub fn integer(n: i128) -> Self {
+        Self { numer: n, denom: 1 }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self::integer(n as i128)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        Rational::new(self.numer * rhs.denom + rhs.numer * self.denom, self.denom * rhs.denom)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        Rational::new(self.numer * rhs.numer, self.denom * rhs.denom)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+/// `n` for integers, `n/d` otherwise.
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+    }
+
+    #[test]
+    fn extended_gcd_finds_bezout_coefficients() {
+        for (a, b) in [(240, 46), (46, 240), (-12, 18), (7, 0), (0, 0), (1_000_000_007, 998_244_353)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g, "{} {}", a, b);
+            assert_eq!(g, gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64) as i128);
+        }
+    }
+
+    #[test]
+    fn crt_with_coprime_moduli() {
+        let solution = crt([Congruence::new(2, 3), Congruence::new(3, 5), Congruence::new(2, 7)]).unwrap();
+        assert_eq!(solution, Congruence::new(23, 105));
+    }
+
+    #[test]
+    fn crt_with_shared_factors() {
+        assert_eq!(Congruence::new(5, 6).combine(&Congruence::new(3, 4)), Some(Congruence::new(11, 12)));
+        assert_eq!(Congruence::new(1, 6).combine(&Congruence::new(2, 4)), None);
+        assert_eq!(Congruence::new(3, 4).combine(&Congruence::new(7, 8)), Some(Congruence::new(7, 8)));
+    }
+
+    #[test]
+    fn crt_matches_brute_force() {
+        for m in 1..13 {
+            for n in 1..13 {
+                for a in 0..m {
+                    for b in 0..n {
+                        let expected = (0..m * n).find(|x| x % m == a && x % n == b);
+                        let combined = Congruence::new(a, m).combine(&Congruence::new(b, n));
+                        assert_eq!(combined.map(|c| c.residue), expected, "{} mod {}, {} mod {}", a, m, b, n);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn first_member_at_least() {
+        let c = Congruence::new(3, 7);
+        assert_eq!(c.first_at_least(0), Some(3));
+        assert_eq!(c.first_at_least(4), Some(10));
+        assert_eq!(c.first_at_least(10), Some(10));
+        assert!(c.contains(17));
+    }
+
+    #[test]
+    fn rationals_stay_in_lowest_terms() {
+        let half = Rational::new(2, 4);
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Rational::new(3, -6), Rational::new(-1, 2));
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!(half - half, Rational::ZERO);
+        assert_eq!(Rational::new(2, 3) * Rational::new(3, 4), half);
+        assert_eq!(Rational::new(-4, 2).to_integer(), Some(-2));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(Rational::new(-3, 6).to_string(), "-1/2");
+        assert_eq!(Rational::from(7).to_string(), "7");
+    }
+}
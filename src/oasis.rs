@@ -0,0 +1,198 @@
+use crate::number_theory::Rational;
+
+/// A sequence followed by its rows of successive differences, each one value shorter than the
+/// row above, stopping before the first row of zeros (or when a row runs out of values).
+///
+/// The table describes the lowest degree polynomial through the values, taking the first value
+/// to be at x = 0, the second at x = 1 and so on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DifferenceTable {
+    rows: Vec<Vec<i64>>,
+}
+
+impl DifferenceTable {
+    /// Table of `values`, or `None` if one of the differences does not fit in 64 bits.
+    pub fn new(values: &[i64]) -> Option<Self> {
+        let mut rows = vec![values.to_vec()];
+        loop {
+            let row = rows
+                .last()
+                .unwrap()
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]))
+                .collect::<Option<Vec<i64>>>()?;
+            if row.iter().all(|x| *x == 0) {
+                break;
+            }
+            rows.push(row);
+        }
+        Some(Self { rows })
+    }
+
+    /// The values, then each row of differences.
+    pub fn rows(&self) -> &[Vec<i64>] {
+        &self.rows
+    }
+
+    /// Degree of the polynomial through the values. An empty sequence or a row of zeros gives
+    /// degree 0.
+    pub fn degree(&self) -> usize {
+        self.rows.len().saturating_sub(1)
+    }
+
+    /// Value after the last one: the sum of the last value of every row. `None` if it does not
+    /// fit in 64 bits.
+    pub fn next(&self) -> Option<i64> {
+        self.rows
+            .iter()
+            .filter_map(|row| row.last())
+            .try_fold(0i64, |acc, x| acc.checked_add(*x))
+    }
+
+    /// Value before the first one: the first values of the rows with alternating signs. `None`
+    /// if it does not fit in 64 bits.
+    pub fn previous(&self) -> Option<i64> {
+        self.rows
+            .iter()
+            .rev()
+            .filter_map(|row| row.first())
+            .try_fold(0i64, |acc, x| x.checked_sub(acc))
+    }
+
+    /// Value `steps` places after the last one, or before the first one when `steps` is
+    /// negative. `None` if the result does not fit in 64 bits.
+    pub fn predict(&self, steps: i64) -> Option<i64> {
+        let last = self.rows[0].len() as i64 - 1;
+        if steps < 0 {
+            self.value_at(steps)
+        } else {
+            self.value_at(last.checked_add(steps)?)
+        }
+    }
+
+    /// Value at position `x`, the first value being at 0. Uses Newton's forward formula: the
+    /// sum over rows `j` of the row's first value times `binomial(x, j)`, which also holds for
+    /// negative `x`. `None` if the result does not fit in 64 bits.
+    pub fn value_at(&self, x: i64) -> Option<i64> {
+        let x = x as i128;
+        let mut binomial: i128 = 1;
+        let mut total: i128 = 0;
+        for (j, row) in self.rows.iter().enumerate() {
+            let Some(first) = row.first() else { break };
+            total = total.checked_add(binomial.checked_mul(*first as i128)?)?;
+            // binomial(x, j + 1) = binomial(x, j) * (x - j) / (j + 1), always a whole number.
+            binomial = binomial.checked_mul(x - j as i128)? / (j as i128 + 1);
+        }
+        i64::try_from(total).ok()
+    }
+
+    /// Coefficients of the polynomial through the values, constant term first, as exact
+    /// fractions. Trailing zero coefficients are left out.
+    pub fn coefficients(&self) -> Vec<Rational> {
+        let mut coefficients = vec![Rational::ZERO; self.rows.len()];
+        // x (x - 1) ... (x - j + 1), constant term first.
+        let mut falling = vec![1i128];
+        let mut factorial: i128 = 1;
+        for (j, row) in self.rows.iter().enumerate() {
+            let Some(first) = row.first() else { break };
+            if j > 0 {
+                factorial *= j as i128;
+            }
+            let scale = Rational::new(*first as i128, factorial);
+            for (power, c) in falling.iter().enumerate() {
+                coefficients[power] = coefficients[power] + scale * Rational::integer(*c);
+            }
+            falling = multiply_by_root(&falling, j as i128);
+        }
+        while coefficients.last().is_some_and(Rational::is_zero) {
+            coefficients.pop();
+        }
+        coefficients
+    }
+}
+
+/// Multiplies a polynomial (constant term first) by `x - root`.
+fn multiply_by_root(poly: &[i128], root: i128) -> Vec<i128> {
+    let mut product = vec![0; poly.len() + 1];
+    for (power, c) in poly.iter().enumerate() {
+        product[power + 1] += c;
+        product[power] -= c * root;
+    }
+    product
+}
+
+/// Value of the polynomial with `coefficients` (constant term first) at `x`.
+pub fn evaluate(coefficients: &[Rational], x: i64) -> Rational {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Rational::ZERO, |acc, c| acc * Rational::from(x) + *c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_rows_stop_before_zeros() {
+        let table = DifferenceTable::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+        assert_eq!(table.rows(), [vec![1, 3, 6, 10, 15, 21], vec![2, 3, 4, 5, 6], vec![1, 1, 1, 1]]);
+        assert_eq!(table.degree(), 2);
+        assert_eq!(DifferenceTable::new(&[5, 5, 5]).unwrap().degree(), 0);
+    }
+
+    #[test]
+    fn next_and_previous() {
+        let table = DifferenceTable::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(table.next(), Some(68));
+        assert_eq!(table.previous(), Some(5));
+        assert_eq!(DifferenceTable::new(&[4]).unwrap().next(), Some(4));
+        assert_eq!(DifferenceTable::new(&[]).unwrap().next(), Some(0));
+    }
+
+    #[test]
+    fn prediction_matches_stepping_one_value_at_a_time() {
+        let mut values = vec![10, 13, 16, 21, 30, 45];
+        let table = DifferenceTable::new(&values).unwrap();
+        for steps in 1..10 {
+            let next = DifferenceTable::new(&values).unwrap().next().unwrap();
+            values.push(next);
+            assert_eq!(table.predict(steps), Some(next), "{} steps", steps);
+        }
+        assert_eq!(table.predict(0), Some(45));
+        let mut values = vec![10, 13, 16, 21, 30, 45];
+        for steps in 1..10 {
+            let previous = DifferenceTable::new(&values).unwrap().previous().unwrap();
+            values.insert(0, previous);
+            assert_eq!(table.predict(-steps), Some(previous), "{} steps back", steps);
+        }
+    }
+
+    #[test]
+    fn prediction_reports_overflow() {
+        let table = DifferenceTable::new(&[0, 1, 4, 9]).unwrap();
+        assert_eq!(table.predict(1_000_000), Some(1_000_003i64.pow(2)));
+        assert_eq!(table.predict(i64::MAX / 2), None);
+    }
+
+    #[test]
+    fn differences_and_extrapolation_report_overflow() {
+        assert_eq!(DifferenceTable::new(&[i64::MIN, i64::MAX]), None);
+        let table = DifferenceTable::new(&[i64::MAX - 1, i64::MAX]).unwrap();
+        assert_eq!(table.next(), None);
+        assert_eq!(table.previous(), Some(i64::MAX - 2));
+        assert_eq!(DifferenceTable::new(&[i64::MIN, i64::MIN + 1]).unwrap().previous(), None);
+    }
+
+    #[test]
+    fn coefficients_are_exact() {
+        // Triangular numbers starting at 1: (x + 1)(x + 2) / 2 = 1 + 3/2 x + 1/2 x^2.
+        let table = DifferenceTable::new(&[1, 3, 6, 10, 15, 21]).unwrap();
+        let coefficients = table.coefficients();
+        assert_eq!(coefficients, [Rational::ONE, Rational::new(3, 2), Rational::new(1, 2)]);
+        for x in -5..20 {
+            assert_eq!(evaluate(&coefficients, x).to_integer(), table.value_at(x).map(i128::from));
+        }
+        assert_eq!(DifferenceTable::new(&[0, 0]).unwrap().coefficients(), []);
+    }
+}
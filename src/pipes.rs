@@ -0,0 +1,326 @@
+use std::{error, fmt};
+
+use crate::{
+    geometry::{Direction, Point},
+    grid::Grid,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TileKind {
+    Pipe(Direction, Direction),
+    Start,
+    Empty,
+}
+
+/// Every pipe shape with its label, openings in the order the label suggests.
+const PIPES: [(char, Direction, Direction); 6] = [
+    ('|', Direction::North, Direction::South),
+    ('-', Direction::West, Direction::East),
+    ('L', Direction::North, Direction::East),
+    ('J', Direction::North, Direction::West),
+    ('7', Direction::South, Direction::West),
+    ('F', Direction::South, Direction::East),
+];
+
+impl TileKind {
+    /// The pipe joining `a` and `b`, in the same form the parser produces, if they differ.
+    pub fn pipe_between(a: Direction, b: Direction) -> Option<Self> {
+        PIPES
+            .iter()
+            .find(|(_, d1, d2)| (*d1, *d2) == (a, b) || (*d1, *d2) == (b, a))
+            .map(|(_, d1, d2)| Self::Pipe(*d1, *d2))
+    }
+
+    pub fn opens_towards(&self, dir: Direction) -> bool {
+        matches!(self, Self::Pipe(d1, d2) if *d1 == dir || *d2 == dir)
+    }
+
+    /// Direction a pipe entered moving towards `dir` is left by, if the pipe accepts it.
+    pub fn exit(&self, dir: Direction) -> Option<Direction> {
+        match *self {
+            Self::Pipe(d1, d2) if d1 == dir.opposite() => Some(d2),
+            Self::Pipe(d1, d2) if d2 == dir.opposite() => Some(d1),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<char> for TileKind {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            'S' => Ok(Self::Start),
+            _ => PIPES
+                .iter()
+                .find(|(label, _, _)| *label == value)
+                .map(|(_, d1, d2)| Self::Pipe(*d1, *d2))
+                .ok_or(value),
+        }
+    }
+}
+
+impl From<&TileKind> for char {
+    fn from(value: &TileKind) -> char {
+        match value {
+            TileKind::Pipe(Direction::North, Direction::South) => '│',
+            TileKind::Pipe(Direction::West, Direction::East) => '─',
+            TileKind::Pipe(Direction::North, Direction::East) => '└',
+            TileKind::Pipe(Direction::North, Direction::West) => '┘',
+            TileKind::Pipe(Direction::South, Direction::West) => '┐',
+            TileKind::Pipe(Direction::South, Direction::East) => '┌',
+            TileKind::Empty => 'O',
+            TileKind::Start => '╋',
+            _ => '?',
+        }
+    }
+}
+
+/// The pipe field and the position of the start tile.
+#[derive(Clone, Debug)]
+pub struct TileMap {
+    pub tiles: Grid<TileKind>,
+    pub start: Point,
+}
+
+impl TileMap {
+    /// The tiles with the start replaced by the pipe it hides, see [`infer_start`].
+    pub fn resolved(&self) -> Result<Grid<TileKind>, PipeError> {
+        let mut tiles = self.tiles.clone();
+        tiles[self.start] = infer_start(self)?;
+        Ok(tiles)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PipeError {
+    /// No closed loop goes through the start tile.
+    NoLoop { start: Point },
+    /// Several loops go through the start tile, so its pipe could have any of these shapes.
+    Ambiguous { start: Point, shapes: Vec<TileKind> },
+}
+
+impl fmt::Display for PipeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoLoop { start } => {
+                write!(f, "No loop goes through the start tile at line {}, column {}", start.row + 1, start.col + 1)
+            }
+            Self::Ambiguous { start, shapes } => {
+                let labels: String = shapes.iter().map(char::from).collect();
+                write!(
+                    f,
+                    "The start tile at line {}, column {} closes {} different loops ({})",
+                    start.row + 1,
+                    start.col + 1,
+                    shapes.len(),
+                    labels
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for PipeError {}
+
+/// A closed loop of pipes, in walking order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PipeLoop {
+    path: Vec<Point>,
+}
+
+impl PipeLoop {
+    pub fn path(&self) -> &[Point] {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// Tile farthest along the loop from its first tile, and how many steps away it is
+    /// whichever way round the loop goes.
+    pub fn farthest(&self) -> (Point, usize) {
+        let steps = self.path.len() / 2;
+        (self.path[steps], steps)
+    }
+}
+
+/// Shape of the pipe under the start tile. Only neighbours opening towards the start are
+/// considered, and of those only pairs that lead back to each other, so dead ends touching the
+/// start do not count.
+pub fn infer_start(map: &TileMap) -> Result<TileKind, PipeError> {
+    let connected: Vec<Direction> = Direction::ALL
+        .into_iter()
+        .filter(|dir| map.tiles.get(map.start + *dir).is_some_and(|tile| tile.opens_towards(dir.opposite())))
+        .collect();
+
+    let mut shapes = vec![];
+    for (i, first) in connected.iter().enumerate() {
+        for second in &connected[i + 1..] {
+            let Some(shape) = TileKind::pipe_between(*first, *second) else {
+                continue;
+            };
+            let mut tiles = map.tiles.clone();
+            tiles[map.start] = shape;
+            if trace(&tiles, map.start).is_some() {
+                shapes.push(shape);
+            }
+        }
+    }
+
+    match shapes.as_slice() {
+        [] => Err(PipeError::NoLoop { start: map.start }),
+        [shape] => Ok(*shape),
+        _ => Err(PipeError::Ambiguous { start: map.start, shapes }),
+    }
+}
+
+/// The loop through the start tile, beginning there.
+pub fn start_loop(map: &TileMap) -> Result<PipeLoop, PipeError> {
+    let tiles = map.resolved()?;
+    trace(&tiles, map.start).ok_or(PipeError::NoLoop { start: map.start })
+}
+
+/// Every closed loop of `tiles`, each starting at its first tile in row order. The start tile
+/// only takes part once resolved, see [`TileMap::resolved`].
+pub fn all_loops(tiles: &Grid<TileKind>) -> Vec<PipeLoop> {
+    let mut seen = tiles.map(|_| false);
+    let mut loops = vec![];
+    for (pos, tile) in tiles.iter() {
+        if seen[pos] || !matches!(tile, TileKind::Pipe(..)) {
+            continue;
+        }
+        match trace(tiles, pos) {
+            Some(found) => {
+                for p in found.path() {
+                    seen[*p] = true;
+                }
+                loops.push(found);
+            }
+            // The whole chain is open, none of it is worth tracing again.
+            None => {
+                for p in walk(tiles, pos).0 {
+                    seen[p] = true;
+                }
+            }
+        }
+    }
+    loops
+}
+
+/// The loop through the pipe at `from`, if following it leads back there.
+fn trace(tiles: &Grid<TileKind>, from: Point) -> Option<PipeLoop> {
+    match walk(tiles, from) {
+        (path, true) => Some(PipeLoop { path }),
+        (_, false) => None,
+    }
+}
+
+/// Tiles visited following the pipe at `from` out of its first opening, and whether the walk
+/// came back to `from`.
+fn walk(tiles: &Grid<TileKind>, from: Point) -> (Vec<Point>, bool) {
+    let TileKind::Pipe(mut dir, _) = tiles[from] else {
+        return (vec![from], false);
+    };
+    let mut path = vec![from];
+    let mut pos = from;
+    loop {
+        pos = pos + dir;
+        if pos == from {
+            // Arriving through the other opening closes the loop.
+            return (path, tiles[from].exit(dir).is_some());
+        }
+        match tiles.get(pos).and_then(|tile| tile.exit(dir)) {
+            Some(next) => {
+                path.push(pos);
+                dir = next;
+            }
+            None => return (path, false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile_map(input: &str) -> TileMap {
+        let tiles = Grid::parse(input, |c| TileKind::try_from(c).ok()).unwrap();
+        let start = tiles.iter().find(|(_, t)| **t == TileKind::Start).map(|(p, _)| p).unwrap();
+        TileMap { tiles, start }
+    }
+
+    const DEAD_ENDS: &str = "\
+.|...
+-S-7.
+.|.|.
+.L-J.
+.....
+";
+
+    const FIGURE_EIGHT: &str = "\
+F-7..
+|.|..
+L-S-7
+..|.|
+..L-J
+";
+
+    const TWO_LOOPS: &str = "\
+S7.F7
+LJ.LJ
+.....
+F-7..
+L-J..
+";
+
+    #[test]
+    fn dead_ends_next_to_the_start_are_ignored() {
+        let map = tile_map(DEAD_ENDS);
+        assert_eq!(infer_start(&map), Ok(TileKind::try_from('F').unwrap()));
+        let found = start_loop(&map).unwrap();
+        assert_eq!(found.len(), 8);
+        assert_eq!(found.path()[0], map.start);
+        assert_eq!(found.farthest(), (Point::new(3, 3), 4));
+    }
+
+    #[test]
+    fn two_loops_through_the_start_are_ambiguous() {
+        let map = tile_map(FIGURE_EIGHT);
+        let err = infer_start(&map).unwrap_err();
+        let shapes = [TileKind::try_from('J').unwrap(), TileKind::try_from('F').unwrap()];
+        assert_eq!(err, PipeError::Ambiguous { start: Point::new(2, 2), shapes: shapes.to_vec() });
+        assert_eq!(err.to_string(), "The start tile at line 3, column 3 closes 2 different loops (┘┌)");
+    }
+
+    #[test]
+    fn start_without_a_loop() {
+        let map = tile_map("S-.\n...");
+        assert_eq!(start_loop(&map), Err(PipeError::NoLoop { start: Point::ORIGIN }));
+    }
+
+    #[test]
+    fn finds_every_loop() {
+        let map = tile_map(TWO_LOOPS);
+        assert!(all_loops(&map.tiles).iter().all(|l| !l.path().contains(&map.start)));
+        let loops = all_loops(&map.resolved().unwrap());
+        let sizes: Vec<(Point, usize)> = loops.iter().map(|l| (l.path()[0], l.len())).collect();
+        assert_eq!(sizes, [(Point::new(0, 0), 4), (Point::new(0, 3), 4), (Point::new(3, 0), 6)]);
+    }
+
+    #[test]
+    fn paths_move_one_step_at_a_time() {
+        let map = tile_map(DEAD_ENDS);
+        let found = start_loop(&map).unwrap();
+        let path = found.path();
+        for (a, b) in path.iter().zip(path.iter().cycle().skip(1)) {
+            assert_eq!(a.manhattan(*b), 1);
+        }
+    }
+}
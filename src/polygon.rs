@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use crate::geometry::{Direction, Point};
+
+/// Twice the area enclosed by `vertices` (the Shoelace formula), which is always a whole
+/// number for lattice points. The polygon is closed from the last vertex back to the first and
+/// may run either way round.
+pub fn double_area(vertices: &[Point]) -> u64 {
+    let cross: i64 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| a.col * b.row - b.col * a.row)
+        .sum();
+    cross.unsigned_abs()
+}
+
+/// Lattice points strictly inside a closed path of unit steps, from Pick's theorem:
+/// `A = I + B / 2 - 1` where every point of the path is one of the `B` boundary points.
+/// A path that doubles back on itself counts its points more than once and encloses nothing.
+pub fn interior_count(path: &[Point]) -> u64 {
+    if path.len() < 4 {
+        return 0;
+    }
+    (double_area(path) + 2).saturating_sub(path.len() as u64) / 2
+}
+
+/// Lattice points strictly inside a closed path of unit steps, found by scanning each row from
+/// the left and flipping between outside and inside at every path point connected northwards.
+pub fn scanline_interior(path: &[Point]) -> Vec<Point> {
+    let on_path: HashSet<Point> = path.iter().copied().collect();
+    let north_connected: HashSet<Point> = path
+        .iter()
+        .zip(path.iter().cycle().skip(1))
+        .filter_map(|(a, b)| {
+            if b.step(Direction::North) == *a {
+                Some(*b)
+            } else if a.step(Direction::North) == *b {
+                Some(*a)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let (Some(min_row), Some(max_row)) = (path.iter().map(|p| p.row).min(), path.iter().map(|p| p.row).max()) else {
+        return vec![];
+    };
+    let min_col = path.iter().map(|p| p.col).min().unwrap();
+    let max_col = path.iter().map(|p| p.col).max().unwrap();
+
+    let mut inside_points = vec![];
+    for row in min_row..=max_row {
+        let mut inside = false;
+        for col in min_col..=max_col {
+            let pos = Point::new(row, col);
+            if north_connected.contains(&pos) {
+                inside = !inside;
+            } else if inside && !on_path.contains(&pos) {
+                inside_points.push(pos);
+            }
+        }
+    }
+    inside_points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Closed path walking `moves` (N/E/S/W letters) from the origin.
+    fn walk(moves: &str) -> Vec<Point> {
+        let mut pos = Point::ORIGIN;
+        let mut path = vec![];
+        for m in moves.chars() {
+            path.push(pos);
+            let dir = match m {
+                'N' => Direction::North,
+                'E' => Direction::East,
+                'S' => Direction::South,
+                _ => Direction::West,
+            };
+            pos = pos.step(dir);
+        }
+        assert_eq!(pos, Point::ORIGIN, "{} is not closed", moves);
+        path
+    }
+
+    #[test]
+    fn shoelace_ignores_orientation() {
+        let square = [Point::new(0, 0), Point::new(0, 3), Point::new(3, 3), Point::new(3, 0)];
+        assert_eq!(double_area(&square), 18);
+        let reversed: Vec<Point> = square.iter().rev().copied().collect();
+        assert_eq!(double_area(&reversed), 18);
+    }
+
+    #[test]
+    fn square_ring() {
+        // 3x3 ring around a single point.
+        let path = walk("EESSWWNN");
+        assert_eq!(interior_count(&path), 1);
+        assert_eq!(scanline_interior(&path), [Point::new(1, 1)]);
+    }
+
+    #[test]
+    fn degenerate_paths_enclose_nothing() {
+        assert_eq!(interior_count(&[]), 0);
+        assert_eq!(interior_count(&walk("EW")), 0);
+        assert_eq!(interior_count(&walk("ESWN")), 0);
+        assert!(scanline_interior(&walk("ESWN")).is_empty());
+    }
+
+    #[test]
+    fn backtracking_paths_enclose_nothing() {
+        assert_eq!(interior_count(&walk("EEWW")), 0);
+        assert_eq!(interior_count(&walk("EEEWWSNW")), 0);
+        assert!(scanline_interior(&walk("EEWW")).is_empty());
+    }
+
+    #[test]
+    fn both_methods_agree_on_concave_paths() {
+        for moves in [
+            "EEEESSSSWNNNWSSSWWNNNN",
+            "EEEEEESSWWSSEESSWWWWWWNNEENNWWNN",
+            "EESESSWWNWNN",
+            "EEESSSWNWSWNNN",
+            "ESESESWWWNNN",
+        ] {
+            let path = walk(moves);
+            assert_eq!(interior_count(&path), scanline_interior(&path).len() as u64, "{}", moves);
+        }
+    }
+}
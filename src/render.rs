@@ -0,0 +1,131 @@
+use colored::{control::SHOULD_COLORIZE, Color};
+
+use crate::{geometry::Point, grid::Grid};
+
+/// Colours a rendered cell can be drawn in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Grey,
+}
+
+impl Colour {
+    pub fn terminal(&self) -> Color {
+        match self {
+            Self::Red => Color::Red,
+            Self::Green => Color::Green,
+            Self::Yellow => Color::Yellow,
+            Self::Blue => Color::Blue,
+            Self::Magenta => Color::Magenta,
+            Self::Cyan => Color::Cyan,
+            Self::Grey => Color::BrightBlack,
+        }
+    }
+}
+
+/// Whether rendering adds terminal colour codes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    Plain,
+    Coloured,
+}
+
+impl Mode {
+    /// Coloured when stdout is a terminal, unless `NO_COLOR` or `CLICOLOR` say otherwise.
+    pub fn for_stdout() -> Self {
+        if SHOULD_COLORIZE.should_colorize() {
+            Self::Coloured
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// A picture of a puzzle grid: every cell is drawn as one glyph, optionally coloured.
+pub trait Render {
+    type Cell;
+
+    fn grid(&self) -> &Grid<Self::Cell>;
+
+    fn glyph(&self, pos: Point, cell: &Self::Cell) -> char;
+
+    /// Colour of the cell's glyph; `None` leaves it in the terminal's default colour.
+    fn colour(&self, _pos: Point, _cell: &Self::Cell) -> Option<Colour> {
+        None
+    }
+
+    /// One line per row. Runs of the same colour share a single escape sequence.
+    fn render(&self, mode: Mode) -> String {
+        let grid = self.grid();
+        let mut out = String::new();
+        for row in 0..grid.rows() {
+            let mut run = String::new();
+            let mut current = None;
+            for col in 0..grid.cols() {
+                let pos = Point::new(row as i64, col as i64);
+                let cell = &grid[pos];
+                let colour = self.colour(pos, cell);
+                if colour != current {
+                    out.push_str(&paint(&run, current, mode));
+                    run.clear();
+                    current = colour;
+                }
+                run.push(self.glyph(pos, cell));
+            }
+            out.push_str(&paint(&run, current, mode));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// `text` in `colour`, or as is in plain mode.
+pub fn paint(text: &str, colour: Option<Colour>, mode: Mode) -> String {
+    match (colour, mode) {
+        (Some(colour), Mode::Coloured) if !text.is_empty() => {
+            format!("\x1b[{}m{}\x1b[0m", colour.terminal().to_fg_str(), text)
+        }
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Digits in yellow, everything else as is.
+    struct Digits(Grid<char>);
+
+    impl Render for Digits {
+        type Cell = char;
+
+        fn grid(&self) -> &Grid<char> {
+            &self.0
+        }
+
+        fn glyph(&self, _pos: Point, cell: &char) -> char {
+            *cell
+        }
+
+        fn colour(&self, _pos: Point, cell: &char) -> Option<Colour> {
+            cell.is_ascii_digit().then_some(Colour::Yellow)
+        }
+    }
+
+    #[test]
+    fn plain_mode_has_no_escapes() {
+        let view = Digits(Grid::parse("a12\n3bc", Some).unwrap());
+        assert_eq!(view.render(Mode::Plain), "a12\n3bc\n");
+    }
+
+    #[test]
+    fn coloured_runs_share_one_escape() {
+        let view = Digits(Grid::parse("a12\n3bc", Some).unwrap());
+        assert_eq!(view.render(Mode::Coloured), "a\x1b[33m12\x1b[0m\n\x1b[33m3\x1b[0mbc\n");
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::Instant;
+
+use anyhow::Result;
+
+use crate::{
+    input::InputSource,
+    ledger::{Check, Ledger},
+    solution::{Part, Solution},
+};
+
+/// Solves the requested parts and prints one line per part. Answers computed from the day's own
+/// input are compared against the ledger; returns how many of them regressed.
+pub fn run_day(solution: &dyn Solution, parts: &[Part], source: &InputSource) -> Result<usize> {
+    let input = source.read()?;
+    let ledger = ledger_for(source)?;
+
+    let mut regressions = 0;
+    for part in parts {
+        let start = Instant::now();
+        let answer = solution.solve(*part, &input)?;
+        let elapsed = start.elapsed();
+        println!(
+            "Day {:02} part {}: {} ({:.2?}){}",
+            solution.day(),
+            part,
+            answer,
+            elapsed,
+            check_suffix(ledger.as_ref(), solution.day(), *part, &answer, &mut regressions)
+        );
+    }
+
+    Ok(regressions)
+}
+
+/// Prints the answer of a `dNNpM` binary, flagging it if it disagrees with the ledger.
+pub fn report_answer(source: &InputSource, day: u8, part: Part, answer: impl ToString) -> Result<()> {
+    let answer = answer.to_string();
+    let ledger = ledger_for(source)?;
+
+    let mut regressions = 0;
+    println!(
+        "Answer: {}{}",
+        answer,
+        check_suffix(ledger.as_ref(), day, part, &answer, &mut regressions)
+    );
+
+    if regressions > 0 {
+        anyhow::bail!("Day {:02} part {} regressed", day, part);
+    }
+    Ok(())
+}
+
+/// The ledger only describes the puzzle inputs, so other inputs are never checked.
+fn ledger_for(source: &InputSource) -> Result<Option<Ledger>> {
+    match source {
+        InputSource::Day(_) => Ledger::load().map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn check_suffix(ledger: Option<&Ledger>, day: u8, part: Part, answer: &str, regressions: &mut usize) -> String {
+    match ledger.map(|l| l.check(day, part, answer)) {
+        Some(Check::Confirmed) => " [verified]".to_string(),
+        Some(Check::Regression { expected }) => {
+            *regressions += 1;
+            format!(" [REGRESSION: expected {}]", expected)
+        }
+        Some(Check::Unverified) | None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+    use crate::ledger::{Entry, Verdict};
+
+    #[test]
+    fn regressions_are_flagged_and_counted() {
+        let path = env::temp_dir().join(format!("advent2023-runner-{}.tsv", std::process::id()));
+        fs::write(&path, "2\t1\tcorrect\t8\n").unwrap();
+        let mut ledger = Ledger::load_from(path.clone()).unwrap();
+        ledger
+            .record(Entry {
+                day: 2,
+                part: Part::Two,
+                answer: "7".to_string(),
+                verdict: Verdict::TooLow,
+            })
+            .unwrap();
+        fs::remove_file(path).unwrap();
+
+        let mut regressions = 0;
+        assert_eq!(check_suffix(Some(&ledger), 2, Part::One, "8", &mut regressions), " [verified]");
+        assert_eq!(check_suffix(Some(&ledger), 2, Part::Two, "9", &mut regressions), "");
+        assert_eq!(check_suffix(None, 2, Part::One, "9", &mut regressions), "");
+        assert_eq!(regressions, 0);
+        assert_eq!(
+            check_suffix(Some(&ledger), 2, Part::One, "9", &mut regressions),
+            " [REGRESSION: expected 8]"
+        );
+        assert_eq!(regressions, 1);
+    }
+}
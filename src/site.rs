@@ -0,0 +1,453 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::{
+    http::{client_for, HttpClient, Request, Response},
+    input::{input_path_in, inputs_dir},
+    ledger::Verdict,
+    solution::Part,
+};
+
+pub const YEAR: u16 = 2023;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_USER_AGENT: &str =
+    "github.com/mcmacker4/Advent2023 (advent2023 aoc tool; set AOC_USER_AGENT to add a contact)";
+const DEFAULT_THROTTLE_SECS: u64 = 5;
+
+/// Settings for talking to the puzzle site. Every value is read from the environment first and
+/// then from the `key = value` config file at `$AOC_CONFIG` (default `~/.config/advent2023/config`).
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub user_agent: String,
+    pub throttle: Duration,
+    /// Where inputs are downloaded to, also holding the stamp file used for throttling.
+    pub inputs: PathBuf,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let file = match config_path() {
+            Some(path) if path.exists() => fs::read_to_string(&path)
+                .with_context(|| format!("Could not read config file {}", path.display()))?,
+            _ => String::new(),
+        };
+        let lookup = |env_key: &str, file_key: &str| {
+            env::var(env_key)
+                .ok()
+                .or_else(|| config_value(&file, file_key))
+                .filter(|v| !v.is_empty())
+        };
+
+        let throttle_secs = match lookup("AOC_THROTTLE_SECS", "throttle_secs") {
+            Some(secs) => secs
+                .parse()
+                .with_context(|| format!("Invalid throttle_secs '{}'", secs))?,
+            None => DEFAULT_THROTTLE_SECS,
+        };
+
+        Ok(Self {
+            session: lookup("AOC_SESSION", "session"),
+            base_url: lookup("AOC_BASE_URL", "base_url")
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            user_agent: lookup("AOC_USER_AGENT", "user_agent")
+                .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+            throttle: Duration::from_secs(throttle_secs),
+            inputs: inputs_dir(),
+        })
+    }
+
+    fn session(&self) -> Result<&str> {
+        self.session
+            .as_deref()
+            .ok_or_else(|| anyhow!("No session token, set AOC_SESSION or 'session' in the config file"))
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    env::var_os("AOC_CONFIG").map(PathBuf::from).or_else(|| {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/advent2023/config"))
+    })
+}
+
+fn config_value(file: &str, key: &str) -> Option<String> {
+    file.lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().to_string())
+}
+
+pub struct Site {
+    config: Config,
+    client: Box<dyn HttpClient>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FetchOutcome {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SubmitOutcome {
+    Verdict(Verdict),
+    /// Answered too recently, with the remaining time if the page states it.
+    Wait(Option<Duration>),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unrecognized(String),
+}
+
+impl Site {
+    pub fn new(config: Config) -> Self {
+        let client = client_for(&config.base_url);
+        Self { config, client }
+    }
+
+    pub fn with_client(config: Config, client: Box<dyn HttpClient>) -> Self {
+        Self { config, client }
+    }
+
+    /// Downloads the input for `day` into the inputs directory unless it is already there.
+    pub fn fetch_input(&self, day: u8) -> Result<FetchOutcome> {
+        let path = input_path_in(&self.config.inputs, day);
+        if path.exists() {
+            return Ok(FetchOutcome::Cached(path));
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.config.base_url, YEAR, day);
+        let response = self.send("GET", &url, None)?;
+        match response.status {
+            200 => {}
+            404 => bail!("Input for day {} is not available yet ({})", day, url),
+            400 | 401 | 500 => bail!(
+                "Server rejected the request for day {} with status {}, is the session token valid?",
+                day,
+                response.status
+            ),
+            status => bail!("Unexpected status {} fetching {}", status, url),
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create inputs directory {}", dir.display()))?;
+        }
+        // Written beside the cache and renamed into place, so an interrupted download never
+        // leaves a truncated input that later runs would take as cached.
+        let partial = path.with_extension(format!("txt.{}.part", std::process::id()));
+        fs::write(&partial, response.body)
+            .with_context(|| format!("Could not write input to {}", partial.display()))?;
+        fs::rename(&partial, &path)
+            .with_context(|| format!("Could not move input into place at {}", path.display()))?;
+
+        Ok(FetchOutcome::Downloaded(path))
+    }
+
+    pub fn submit_answer(&self, day: u8, part: Part, answer: &str) -> Result<SubmitOutcome> {
+        let url = format!("{}/{}/day/{}/answer", self.config.base_url, YEAR, day);
+        let body = format!("level={}&answer={}", part, url_encode(answer));
+        let response = self.send("POST", &url, Some(body))?;
+        if response.status != 200 {
+            bail!("Unexpected status {} submitting to {}", response.status, url);
+        }
+        Ok(parse_submit_response(&response.body))
+    }
+
+    fn send(&self, method: &str, url: &str, body: Option<String>) -> Result<Response> {
+        let mut headers = vec![
+            ("Cookie", format!("session={}", self.config.session()?)),
+            ("User-Agent", self.config.user_agent.clone()),
+        ];
+        if body.is_some() {
+            headers.push(("Content-Type", "application/x-www-form-urlencoded".to_string()));
+        }
+        let request = Request {
+            method,
+            url,
+            headers,
+            body,
+        };
+
+        throttle(&self.config.inputs.join(".last-request"), self.config.throttle)?;
+        self.client
+            .send(&request)
+            .with_context(|| format!("{} {} failed", method, url))
+    }
+}
+
+pub fn parse_submit_response(body: &str) -> SubmitOutcome {
+    if body.contains("That's the right answer") {
+        SubmitOutcome::Verdict(Verdict::Correct)
+    } else if body.contains("You gave an answer too recently") {
+        SubmitOutcome::Wait(parse_wait_time(body))
+    } else if body.contains("You don't seem to be solving the right level") {
+        SubmitOutcome::WrongLevel
+    } else if body.contains("That's not the right answer") {
+        let verdict = if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        };
+        SubmitOutcome::Verdict(verdict)
+    } else {
+        SubmitOutcome::Unrecognized(strip_tags(body))
+    }
+}
+
+/// Reads "You have 1m 3s left to wait" style messages.
+fn parse_wait_time(body: &str) -> Option<Duration> {
+    let (_, rest) = body.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+    time.split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value = value.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+fn strip_tags(body: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Sleeps until at least `interval` has passed since the previous request made by any `aoc`
+/// process, as recorded in the `stamp` file.
+fn throttle(stamp: &Path, interval: Duration) -> Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let last = fs::read_to_string(stamp)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(Duration::from_millis);
+
+    let mut sent_at = now;
+    if let Some(last) = last {
+        let ready_at = last + interval;
+        if ready_at > now {
+            thread::sleep(ready_at - now);
+            sent_at = ready_at;
+        }
+    }
+
+    if let Some(dir) = stamp.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(stamp, sent_at.as_millis().to_string())
+        .with_context(|| format!("Could not write {}", stamp.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    /// Method, URL and body of a request the fake client received.
+    type Sent = (String, String, Option<String>);
+
+    /// Answers every request with the next canned response and remembers what was asked.
+    #[derive(Clone, Default)]
+    struct FakeClient {
+        responses: Rc<RefCell<Vec<Response>>>,
+        requests: Rc<RefCell<Vec<Sent>>>,
+    }
+
+    impl FakeClient {
+        fn answering(responses: Vec<(u16, &str)>) -> Self {
+            let client = Self::default();
+            client.responses.borrow_mut().extend(
+                responses
+                    .into_iter()
+                    .rev()
+                    .map(|(status, body)| Response { status, body: body.to_string() }),
+            );
+            client
+        }
+
+        fn requests(&self) -> Vec<Sent> {
+            self.requests.borrow().clone()
+        }
+    }
+
+    impl HttpClient for FakeClient {
+        fn send(&self, request: &Request) -> Result<Response> {
+            let cookie = request.headers.iter().find(|(name, _)| *name == "Cookie");
+            assert_eq!(cookie.map(|(_, value)| value.as_str()), Some("session=token"));
+            self.requests
+                .borrow_mut()
+                .push((request.method.to_string(), request.url.to_string(), request.body.clone()));
+            self.responses.borrow_mut().pop().ok_or_else(|| anyhow!("No response left"))
+        }
+    }
+
+    /// An empty inputs directory of its own for each test.
+    fn inputs(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("advent2023-site-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn site(inputs: &Path, client: &FakeClient, throttle: Duration) -> Site {
+        let config = Config {
+            session: Some("token".to_string()),
+            base_url: "http://mock".to_string(),
+            user_agent: "tests".to_string(),
+            throttle,
+            inputs: inputs.to_path_buf(),
+        };
+        Site::with_client(config, Box::new(client.clone()))
+    }
+
+    #[test]
+    fn fetch_downloads_once_then_uses_the_cache() {
+        let dir = inputs("fetch");
+        let client = FakeClient::answering(vec![(200, "1 2 3\n")]);
+        let site = site(&dir, &client, Duration::ZERO);
+
+        let path = input_path_in(&dir, 4);
+        assert_eq!(site.fetch_input(4).unwrap(), FetchOutcome::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+        let partial = fs::read_dir(&dir)
+            .unwrap()
+            .any(|entry| entry.unwrap().path().extension().is_some_and(|ext| ext == "part"));
+        assert!(!partial, "partial download left behind");
+        assert_eq!(site.fetch_input(4).unwrap(), FetchOutcome::Cached(path));
+        let expected = ("GET".to_string(), "http://mock/2023/day/4/input".to_string(), None);
+        assert_eq!(client.requests(), [expected]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_reports_error_statuses() {
+        let dir = inputs("status");
+        let client = FakeClient::answering(vec![(404, "Not found"), (400, "Bad session"), (503, "")]);
+        let site = site(&dir, &client, Duration::ZERO);
+        let errors: Vec<String> = (0..3).map(|_| site.fetch_input(9).unwrap_err().to_string()).collect();
+        assert!(errors[0].contains("not available yet"), "{}", errors[0]);
+        assert!(errors[1].contains("session token"), "{}", errors[1]);
+        assert!(errors[2].contains("Unexpected status 503"), "{}", errors[2]);
+        assert!(!input_path_in(&dir, 9).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{}</p></article></main></body></html>", message)
+    }
+
+    #[test]
+    fn every_verdict_is_recognised() {
+        let cases = [
+            ("That's the right answer! You are <em>one gold star</em> closer.", SubmitOutcome::Verdict(Verdict::Correct)),
+            (
+                "That's not the right answer; your answer is too high. Please wait one minute.",
+                SubmitOutcome::Verdict(Verdict::TooHigh),
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmitOutcome::Verdict(Verdict::TooLow),
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                SubmitOutcome::Verdict(Verdict::Incorrect),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                SubmitOutcome::WrongLevel,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying \
+                 again.  You have 1m 5s left to wait.",
+                SubmitOutcome::Wait(Some(Duration::from_secs(65))),
+            ),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(parse_submit_response(&page(message)), expected, "{}", message);
+        }
+        assert_eq!(
+            parse_submit_response(&page("Something <b>new</b>\n  happened")),
+            SubmitOutcome::Unrecognized("Something new happened".to_string())
+        );
+    }
+
+    #[test]
+    fn wait_times() {
+        assert_eq!(parse_wait_time("You have 36s left to wait."), Some(Duration::from_secs(36)));
+        assert_eq!(parse_wait_time("You have 1h 2m 3s left to wait."), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_wait_time("You have a while left to wait."), None);
+        assert_eq!(parse_wait_time("Please wait."), None);
+    }
+
+    #[test]
+    fn answers_are_form_encoded() {
+        assert_eq!(url_encode("-12 a&b=c"), "-12%20a%26b%3Dc");
+        assert_eq!(url_encode("é"), "%C3%A9");
+    }
+
+    #[test]
+    fn submit_end_to_end_with_throttle() {
+        let dir = inputs("submit");
+        let client = FakeClient::answering(vec![
+            (200, &page("That's not the right answer; your answer is too low.")),
+            (200, &page("That's the right answer!")),
+            (502, "Bad gateway"),
+        ]);
+        let interval = Duration::from_millis(300);
+        let site = site(&dir, &client, interval);
+
+        let start = std::time::Instant::now();
+        assert_eq!(site.submit_answer(6, Part::One, "12 3").unwrap(), SubmitOutcome::Verdict(Verdict::TooLow));
+        assert!(dir.join(".last-request").exists());
+        assert_eq!(site.submit_answer(6, Part::Two, "42").unwrap(), SubmitOutcome::Verdict(Verdict::Correct));
+        // The second request waited for the first one's interval to pass, give or take the
+        // millisecond the stamp file rounds away.
+        assert!(start.elapsed() + Duration::from_millis(1) >= interval, "{:?}", start.elapsed());
+        assert!(site.submit_answer(6, Part::Two, "42").unwrap_err().to_string().contains("502"));
+
+        let url = "http://mock/2023/day/6/answer".to_string();
+        assert_eq!(
+            client.requests()[..2],
+            [
+                ("POST".to_string(), url.clone(), Some("level=1&answer=12%203".to_string())),
+                ("POST".to_string(), url, Some("level=2&answer=42".to_string())),
+            ]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::{self, Display};
+
+use anyhow::Result;
+
+use crate::{
+    bench::{measure, DayTimings},
+    d01, d02, d03, d04, d05, d06, d07, d08, d09, d10, d11,
+};
+
+/// A day as the runner sees it: answers as text, straight from the puzzle input. Implemented for
+/// every [`Day`].
+pub trait Solution {
+    fn day(&self) -> u8;
+    fn part1(&self, input: &str) -> Result<String>;
+    fn part2(&self, input: &str) -> Result<String>;
+
+    /// Times parsing `input` and solving each part on the parsed input, `runs` times each.
+    fn bench(&self, input: &str, runs: usize) -> Result<DayTimings>;
+
+    fn solve(&self, part: Part, input: &str) -> Result<String> {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+}
+
+/// A day's own logic: how its input is parsed and how each part is solved from the parsed form.
+pub trait Day {
+    const DAY: u8;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<impl Display>;
+    fn part2(input: &Self::Input) -> Result<impl Display>;
+}
+
+impl<D: Day> Solution for D {
+    fn day(&self) -> u8 {
+        D::DAY
+    }
+
+    fn part1(&self, input: &str) -> Result<String> {
+        Ok(<D as Day>::part1(&D::parse(input)?)?.to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String> {
+        Ok(<D as Day>::part2(&D::parse(input)?)?.to_string())
+    }
+
+    /// Both parts are solved once first, so a failing day reports its error instead of timings.
+    fn bench(&self, input: &str, runs: usize) -> Result<DayTimings> {
+        let parsed = D::parse(input)?;
+        <D as Day>::part1(&parsed)?;
+        <D as Day>::part2(&parsed)?;
+        Ok(DayTimings {
+            parse: measure(runs, || D::parse(input)),
+            part1: measure(runs, || <D as Day>::part1(&parsed)),
+            part2: measure(runs, || <D as Day>::part2(&parsed)),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(n: u8) -> Option<Self> {
+        match n {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+pub const SOLUTIONS: [&dyn Solution; 11] = [
+    &d01::Day01,
+    &d02::Day02,
+    &d03::Day03,
+    &d04::Day04,
+    &d05::Day05,
+    &d06::Day06,
+    &d07::Day07,
+    &d08::Day08,
+    &d09::Day09,
+    &d10::Day10,
+    &d11::Day11,
+];
+
+pub fn find_solution(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().find(|s| s.day() == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parts_by_number() {
+        assert_eq!(Part::ALL.map(|part| Part::from_number(part.number())), [Some(Part::One), Some(Part::Two)]);
+        assert_eq!(Part::from_number(3), None);
+    }
+
+    #[test]
+    fn every_day_is_registered_once() {
+        for day in 1..=11 {
+            assert_eq!(find_solution(day).map(|solution| solution.day()), Some(day));
+            assert_eq!(SOLUTIONS.iter().filter(|solution| solution.day() == day).count(), 1);
+        }
+        assert!(find_solution(12).is_none());
+    }
+
+    #[test]
+    fn solve_runs_the_day_module() {
+        let day06 = find_solution(6).unwrap();
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        assert_eq!(day06.solve(Part::One, input).unwrap(), "288");
+        assert_eq!(day06.solve(Part::Two, input).unwrap(), "71503");
+    }
+}